@@ -0,0 +1,312 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/node-gui-x/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{collections::BTreeMap, path::PathBuf};
+
+use common::{
+    address::Address,
+    chain::{ChainConfig, Destination},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{AppError, ErrorCode},
+    network::Network,
+    storage,
+};
+
+const MAX_SUGGESTIONS: usize = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Contact {
+    label: String,
+    address: String,
+}
+
+/// Labeled addresses, kept apart per network since their addresses are not interchangeable.
+#[derive(Debug)]
+pub struct AddressBook {
+    contacts: BTreeMap<Network, Vec<Contact>>,
+    path: PathBuf,
+}
+
+impl AddressBook {
+    /// Loads the address book from `path`; a missing file means it is empty, an
+    /// unreadable one is set aside by `storage::read_json` before starting empty.
+    pub fn load(path: PathBuf) -> Self {
+        let contacts = storage::read_json(&path).ok().flatten().unwrap_or_default();
+
+        AddressBook { contacts, path }
+    }
+
+    pub fn contacts(&self, network: Network) -> &[Contact] {
+        self.contacts.get(&network).map_or(&[], Vec::as_slice)
+    }
+
+    /// Contacts whose label or address starts with `query`, ignoring case, for autocomplete.
+    pub fn find(&self, network: Network, query: &str) -> Vec<Contact> {
+        let query = query.to_lowercase();
+        self.contacts(network)
+            .iter()
+            .filter(|contact| {
+                contact.label.to_lowercase().starts_with(&query)
+                    || contact.address.to_lowercase().starts_with(&query)
+            })
+            .take(MAX_SUGGESTIONS)
+            .cloned()
+            .collect()
+    }
+
+    pub fn add_contact(
+        &mut self,
+        network: Network,
+        chain_config: &ChainConfig,
+        label: String,
+        address: String,
+    ) -> Result<(), AppError> {
+        check_contact(chain_config, &label, &address)?;
+
+        let contacts = self.contacts.entry(network).or_default();
+        check_unique(contacts, None, &label, &address)?;
+        contacts.push(Contact { label, address });
+
+        self.save()
+    }
+
+    /// Replaces the contact stored under `address`.
+    pub fn update_contact(
+        &mut self,
+        network: Network,
+        chain_config: &ChainConfig,
+        address: &str,
+        contact: Contact,
+    ) -> Result<(), AppError> {
+        check_contact(chain_config, &contact.label, &contact.address)?;
+
+        let contacts = self.contacts.entry(network).or_default();
+        let index = contacts
+            .iter()
+            .position(|existing| existing.address == address)
+            .ok_or_else(not_found)?;
+        check_unique(contacts, Some(index), &contact.label, &contact.address)?;
+        contacts[index] = contact;
+
+        self.save()
+    }
+
+    pub fn delete_contact(&mut self, network: Network, address: &str) -> Result<(), AppError> {
+        let contacts = self.contacts.entry(network).or_default();
+        let index = contacts
+            .iter()
+            .position(|contact| contact.address == address)
+            .ok_or_else(not_found)?;
+        contacts.remove(index);
+
+        self.save()
+    }
+
+    fn save(&self) -> Result<(), AppError> {
+        storage::write_json(&self.path, &self.contacts)
+    }
+}
+
+fn not_found() -> AppError {
+    AppError::invalid_input("The address is not in the address book")
+}
+
+/// Checks that no contact but the one at `skip` has the label, ignoring case, or the
+/// address.
+fn check_unique(
+    contacts: &[Contact],
+    skip: Option<usize>,
+    label: &str,
+    address: &str,
+) -> Result<(), AppError> {
+    let others = contacts.iter().enumerate().filter(|(index, _)| Some(*index) != skip);
+    for (_, contact) in others {
+        if contact.address == address {
+            return Err(AppError::invalid_input(
+                "The address is already in the address book",
+            ));
+        }
+        if contact.label.trim().eq_ignore_ascii_case(label.trim()) {
+            return Err(AppError::invalid_input(format!(
+                "A contact is already labeled {}",
+                contact.label
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Checks the label and that the address belongs to the network of `chain_config`.
+fn check_contact(chain_config: &ChainConfig, label: &str, address: &str) -> Result<(), AppError> {
+    if label.trim().is_empty() {
+        return Err(AppError::invalid_input("The label cannot be empty"));
+    }
+    Address::<Destination>::from_string(chain_config, address)
+        .map_err(|e| AppError::new(ErrorCode::InvalidAddress, e.to_string()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{address::pubkeyhash::PublicKeyHash, chain::config::create_unit_test_config};
+
+    use super::*;
+
+    fn address(chain_config: &ChainConfig, byte: u8) -> String {
+        let destination = Destination::PublicKeyHash(PublicKeyHash::repeat_byte(byte));
+        Address::new(chain_config, destination).unwrap().to_string()
+    }
+
+    fn empty_book(name: &str) -> AddressBook {
+        let dir =
+            std::env::temp_dir().join(format!("node-gui-x-address-book-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        let _ = std::fs::remove_file(&path);
+        AddressBook::load(path)
+    }
+
+    fn labels(contacts: &[Contact]) -> Vec<&str> {
+        contacts.iter().map(|contact| contact.label.as_str()).collect()
+    }
+
+    #[test]
+    fn contacts_are_kept_per_network_and_saved() {
+        let chain_config = create_unit_test_config();
+        let mut book = empty_book("networks.json");
+        let alice = address(&chain_config, 1);
+        book.add_contact(
+            Network::Mainnet,
+            &chain_config,
+            "Alice".to_owned(),
+            alice.clone(),
+        )
+        .unwrap();
+        // The same address may be a contact on both networks
+        book.add_contact(Network::Testnet, &chain_config, "Bob".to_owned(), alice)
+            .unwrap();
+
+        assert_eq!(labels(book.contacts(Network::Mainnet)), ["Alice"]);
+        assert_eq!(labels(book.contacts(Network::Testnet)), ["Bob"]);
+
+        let loaded = AddressBook::load(book.path.clone());
+        assert_eq!(labels(loaded.contacts(Network::Mainnet)), ["Alice"]);
+        assert_eq!(labels(loaded.contacts(Network::Testnet)), ["Bob"]);
+    }
+
+    #[test]
+    fn labels_and_addresses_are_unique() {
+        let chain_config = create_unit_test_config();
+        let mut book = empty_book("unique.json");
+        let mut add = |label: &str, byte| {
+            book.add_contact(
+                Network::Testnet,
+                &chain_config,
+                label.to_owned(),
+                address(&chain_config, byte),
+            )
+        };
+        add("Alice", 1).unwrap();
+        add("Bob", 2).unwrap();
+
+        assert_eq!(add("Carol", 1).unwrap_err().code(), ErrorCode::InvalidInput);
+        assert_eq!(
+            add(" alice ", 3).unwrap_err().code(),
+            ErrorCode::InvalidInput
+        );
+        assert_eq!(add(" ", 3).unwrap_err().code(), ErrorCode::InvalidInput);
+        add("Carol", 3).unwrap();
+
+        let bob = address(&chain_config, 2);
+        let update = |book: &mut AddressBook, label: &str, byte| {
+            let contact = Contact {
+                label: label.to_owned(),
+                address: address(&chain_config, byte),
+            };
+            book.update_contact(Network::Testnet, &chain_config, &bob, contact)
+        };
+        // Keeping its own label or address is fine, taking another contact's is not
+        update(&mut book, "Bob", 2).unwrap();
+        assert_eq!(
+            update(&mut book, "Alice", 2).unwrap_err().code(),
+            ErrorCode::InvalidInput
+        );
+        assert_eq!(
+            update(&mut book, "Bobby", 1).unwrap_err().code(),
+            ErrorCode::InvalidInput
+        );
+    }
+
+    #[test]
+    fn unknown_addresses_cannot_be_updated_or_deleted() {
+        let chain_config = create_unit_test_config();
+        let mut book = empty_book("unknown.json");
+        let alice = address(&chain_config, 1);
+        book.add_contact(
+            Network::Testnet,
+            &chain_config,
+            "Alice".to_owned(),
+            alice.clone(),
+        )
+        .unwrap();
+
+        let unknown = address(&chain_config, 2);
+        let error = book.delete_contact(Network::Testnet, &unknown).unwrap_err();
+        assert_eq!(error.code(), ErrorCode::InvalidInput);
+        let error = book.delete_contact(Network::Mainnet, &alice).unwrap_err();
+        assert_eq!(error.code(), ErrorCode::InvalidInput);
+        let contact = Contact {
+            label: "Bob".to_owned(),
+            address: unknown.clone(),
+        };
+        let error = book
+            .update_contact(Network::Testnet, &chain_config, &unknown, contact)
+            .unwrap_err();
+        assert_eq!(error.code(), ErrorCode::InvalidInput);
+
+        book.delete_contact(Network::Testnet, &alice).unwrap();
+        assert!(book.contacts(Network::Testnet).is_empty());
+    }
+
+    #[test]
+    fn search_matches_label_or_address_prefixes() {
+        let chain_config = create_unit_test_config();
+        let mut book = empty_book("search.json");
+        for (label, byte) in [("Alice", 1), ("alfred", 2), ("Bob", 3)] {
+            book.add_contact(
+                Network::Testnet,
+                &chain_config,
+                label.to_owned(),
+                address(&chain_config, byte),
+            )
+            .unwrap();
+        }
+
+        assert_eq!(
+            labels(&book.find(Network::Testnet, "AL")),
+            ["Alice", "alfred"]
+        );
+        assert_eq!(labels(&book.find(Network::Testnet, "bo")), ["Bob"]);
+        assert!(book.find(Network::Testnet, "lice").is_empty());
+        assert!(book.find(Network::Mainnet, "al").is_empty());
+
+        let bob = address(&chain_config, 3);
+        let prefix = &bob[..bob.len() - 2].to_uppercase();
+        assert_eq!(labels(&book.find(Network::Testnet, prefix)), ["Bob"]);
+    }
+}
@@ -13,87 +13,133 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{path::PathBuf, sync::Arc};
-use tauri::{AppHandle, Emitter, State};
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
 use tokio::sync::mpsc::UnboundedReceiver;
 use tokio::sync::Mutex;
 
 use chainstate::ChainInfo;
 use common::{
     address::Address,
-    chain::ChainConfig,
-    primitives::{BlockCount, BlockHeight},
-    time_getter::TimeGetter,
+    chain::{
+        signature::inputsig::InputWitness, ChainConfig, Destination, SignedTransaction, Transaction,
+    },
+    primitives::{Amount, BlockCount, BlockHeight, Idable},
 };
 use node_gui_backend::{
     error::BackendError,
     messages::{
         BackendEvent, BackendRequest, CreateDelegationRequest, DecommissionPoolRequest,
         DelegateStakingRequest, EncryptionAction, SendDelegateToAddressRequest, SendRequest,
-        StakeRequest, WalletId,
+        SignedTransactionWrapper, StakeRequest, TransactionInfo, WalletId, WalletInfo,
     },
 };
-use node_gui_backend::{ImportOrCreate, InitNetwork, WalletMode};
-use wallet_types::wallet_type::WalletType;
+use node_gui_backend::{AccountId, ImportOrCreate};
+use parity_scale_codec::{DecodeAll, Encode};
+use wallet_types::{
+    partially_signed_transaction::PartiallySignedTransaction, wallet_type::WalletType,
+};
 
-use crate::AppState;
+use crate::{
+    address_book::Contact,
+    compose::{Draft, FeeQuote, Payment, MAX_FEE_ROUNDS},
+    console,
+    error::{AppError, ErrorCode, Rejection},
+    event_cache::EventSnapshot,
+    fee::{FeeEstimate, FeeRate},
+    history::{self, HistoryPage, HistoryQuery},
+    lifecycle::{start_node, stop_node, NodeState},
+    mnemonic::{self, MnemonicValidation},
+    network::Network,
+    nft::NftInfo,
+    payout::{self, PaymentOutput, PayoutPreview},
+    qr_frames::{self, QrDecodeResult},
+    request_tracker::{PendingRequest, RequestId, RequestTracker},
+    settings::{Settings, WalletMode},
+    signing::{self, MessageVerification},
+    token::TokenInfo,
+    transaction_decode::DecodedTransaction,
+    transaction_file::TransactionFile,
+    utxo::{self, UtxoEntry},
+    wallet_registry::{OpenWalletEntry, RecentWallet, WalletRegistry},
+    AppState, EventState,
+};
 
 use super::request::{
-    ConsoleRequest, DecommissionStakingPoolRequest, DelegationCreateRequest, NewAccountRequest,
-    NewAddressRequest, OpenCreateWalletRequest, OpenWalletRequest, SendAmountRequest,
-    SendDelegateRequest, StakeAmountRequest, StakingDelegateRequest, SubmitTransactionRequest,
-    ToggleStakingRequest, UpdateEncryptionRequest,
+    BatchSendRequest, ConsoleRequest, DecommissionStakingPoolRequest, DelegationCreateRequest,
+    IssueNftRequest, IssueTokenRequest, ManageTokenRequest, NewAccountRequest, NewAddressRequest,
+    OpenCreateWalletRequest, OpenWalletRequest, SendAmountRequest, SendDelegateRequest,
+    SendTokenRequest, StakeAmountRequest, StakingDelegateRequest, SubmitTransactionRequest,
+    SweepRequest, ToggleStakingRequest, TransferNftRequest, UpdateEncryptionRequest,
 };
 
 use super::result::{
-    BalanceResult, DelegateStakingResult, DelegationsBalanceResult, StakingBalanceResult,
+    BalanceResult, BroadcastResult, DelegateStakingResult, DelegationsBalanceResult, ErrorEvent,
+    PreparedTransaction, ResponseEvent, StakingBalanceResult, TransactionFileResult,
     TransactionListResult, TransactionResult, WalletBestBlockResult,
 };
 
 #[tauri::command]
-pub async fn initialize_node(
+pub async fn initialize_node<R: Runtime>(
+    app_handle: AppHandle<R>,
     state: State<'_, Mutex<AppState>>,
     network: &str,
     mode: &str,
-) -> Result<ChainInfo, String> {
-    let net_type = match network {
-        "Mainnet" => InitNetwork::Mainnet,
-        "Testnet" => InitNetwork::Testnet,
-        _ => {
-            return Err("Invalid network selection".into());
-        }
-    };
-    let wallet_type = match mode {
-        "Hot" => WalletMode::Hot,
-        "Cold" => WalletMode::Cold,
-        _ => {
-            return Err("Invalid wallet mode selection".into());
-        }
-    };
+) -> Result<ChainInfo, AppError> {
+    let network = Network::from_name(network)?;
+    let wallet_mode = WalletMode::from_name(mode)?;
 
-    let backend_controls =
-        node_gui_backend::node_initialize(TimeGetter::default(), net_type, wallet_type)
-            .await
-            .map_err(|e| e.to_string())?;
+    let chain_info = start_node(&app_handle, &state, network, wallet_mode.to_backend()).await?;
+    remember_node(&app_handle, &state, network, wallet_mode).await;
+    Ok(chain_info)
+}
 
-    let mut app_state = state.lock().await;
-    app_state.backend_sender = Some(backend_controls.backend_sender);
-    app_state.chain_config = Some(backend_controls.initialized_node.chain_config.clone());
+/// Stops the running node, if any, and starts it again with the given network and mode.
+#[tauri::command]
+pub async fn restart_node<R: Runtime>(
+    app_handle: AppHandle<R>,
+    state: State<'_, Mutex<AppState>>,
+    network: &str,
+    mode: &str,
+) -> Result<ChainInfo, AppError> {
+    let network = Network::from_name(network)?;
+    let wallet_mode = WalletMode::from_name(mode)?;
+
+    stop_node(&app_handle, &state).await?;
+    let chain_info = start_node(&app_handle, &state, network, wallet_mode.to_backend()).await?;
+    remember_node(&app_handle, &state, network, wallet_mode).await;
+    Ok(chain_info)
+}
 
-    // TODO: reconsider if the task should be joined
-    tokio::spawn(listen_backend_events(
-        app_state.app_handle.clone(),
-        backend_controls.initialized_node.chain_config.clone(),
-        backend_controls.backend_receiver,
-        backend_controls.low_priority_backend_receiver,
-    ));
+/// Stores the network and mode of a successful start, so the next launch can reuse them.
+///
+/// The node is running at this point, so a failure to save is only reported.
+async fn remember_node<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    state: &Mutex<AppState>,
+    network: Network,
+    wallet_mode: WalletMode,
+) {
+    let mut state = state.lock().await;
+    if let Err(e) = state.settings.set_last_node(network, wallet_mode) {
+        emit_error(app_handle, None, e);
+    }
+}
 
-    Ok(backend_controls.initialized_node.chain_info)
+#[tauri::command]
+pub async fn get_node_state(state: State<'_, Mutex<AppState>>) -> Result<NodeState, AppError> {
+    let state = state.lock().await;
+    Ok(state.node_state)
 }
 
-async fn listen_backend_events(
-    app_handle: AppHandle,
+pub async fn listen_backend_events<R: Runtime>(
+    app_handle: AppHandle<R>,
     chain_config: Arc<ChainConfig>,
+    event_state: Arc<Mutex<EventState>>,
     mut backend_receiver: UnboundedReceiver<BackendEvent>,
     mut low_priority_backend_receiver: UnboundedReceiver<BackendEvent>,
 ) {
@@ -102,10 +148,12 @@ async fn listen_backend_events(
             biased;
 
             Some(event) = backend_receiver.recv() => {
-                process_event(&app_handle, event, &chain_config);
+                let mut event_state = event_state.lock().await;
+                process_event(&app_handle, event, &chain_config, &mut event_state, false);
             }
             Some(event) = low_priority_backend_receiver.recv() => {
-                process_event(&app_handle, event, &chain_config);
+                let mut event_state = event_state.lock().await;
+                process_event(&app_handle, event, &chain_config, &mut event_state, true);
             }
             else => {
                 // Node is stopped
@@ -115,113 +163,313 @@ async fn listen_backend_events(
     }
 }
 
+#[tauri::command]
+pub async fn get_network(state: State<'_, Mutex<AppState>>) -> Result<Option<Network>, AppError> {
+    let state = state.lock().await;
+    Ok(state.network)
+}
+
+/// Returns the latest value of every state event, for windows that missed them.
+#[tauri::command]
+pub async fn get_event_snapshot(
+    state: State<'_, Mutex<AppState>>,
+) -> Result<EventSnapshot, AppError> {
+    let state = state.lock().await;
+    let event_state = state.event_state.lock().await;
+    Ok(event_state.event_cache.snapshot())
+}
+
+#[tauri::command]
+pub async fn get_settings(state: State<'_, Mutex<AppState>>) -> Result<Settings, AppError> {
+    let state = state.lock().await;
+    Ok(state.settings.get().clone())
+}
+
+#[tauri::command]
+pub async fn set_settings(
+    state: State<'_, Mutex<AppState>>,
+    settings: Settings,
+) -> Result<(), AppError> {
+    let mut state = state.lock().await;
+    state.settings.set(settings)
+}
+
 #[tauri::command]
 pub async fn get_stake_pool_maturity_distance(
     state: tauri::State<'_, Mutex<AppState>>,
     best_block_height: BlockHeight,
-) -> Result<BlockCount, String> {
+) -> Result<BlockCount, AppError> {
     let state = state.lock().await;
 
-    Ok(state
-        .chain_config
-        .as_ref()
-        .expect("must be initialized")
-        .staking_pool_spend_maturity_block_count(best_block_height))
+    Ok(state.chain_config()?.staking_pool_spend_maturity_block_count(best_block_height))
 }
 
-fn emit_event_or_error<T>(app_handle: &AppHandle, event_name: &str, r: Result<T, BackendError>)
-where
+fn emit_event_or_error<R: Runtime, T>(
+    app_handle: &AppHandle<R>,
+    event_name: &str,
+    r: Result<T, AppError>,
+) where
     T: serde::Serialize + Clone + std::fmt::Debug,
 {
     match r {
         Ok(data) => {
             app_handle.emit(event_name, data.clone()).expect("Failed to emit backend event");
         }
-        Err(e) => {
-            app_handle.emit("Error", e.to_string()).expect("Failed to emit backend event");
+        Err(e) => emit_error(app_handle, None, e.with_event(event_name)),
+    }
+}
+
+/// Like `emit_event_or_error`, but for events answering a command: the payload carries
+/// the id of the request that produced it.
+fn emit_response_or_error<R: Runtime, T>(
+    app_handle: &AppHandle<R>,
+    request_tracker: &mut RequestTracker,
+    event_name: &str,
+    r: Result<T, BackendError>,
+) where
+    T: serde::Serialize + Clone + std::fmt::Debug,
+{
+    let pending_request = request_tracker.resolve(event_name);
+    emit_to_request(app_handle, pending_request, event_name, r);
+}
+
+/// Like `emit_response_or_error`, for the events about a wallet, or one of its accounts,
+/// that the backend also sends unrequested: only a request for the same wallet and
+/// account is answered, an event nobody asked for is emitted without a request id.
+///
+/// The ids are unknown for an error, which answers the oldest request then.
+fn emit_wallet_response_or_error<R: Runtime, T>(
+    app_handle: &AppHandle<R>,
+    request_tracker: &mut RequestTracker,
+    event_name: &str,
+    wallet_id: Option<WalletId>,
+    account_id: Option<AccountId>,
+    r: Result<T, BackendError>,
+) where
+    T: serde::Serialize + Clone + std::fmt::Debug,
+{
+    let pending_request = match wallet_id {
+        Some(wallet_id) => request_tracker.resolve_matching(event_name, wallet_id, account_id),
+        None => request_tracker.resolve(event_name),
+    };
+    emit_to_request(app_handle, pending_request, event_name, r);
+}
+
+/// Hands the result of a backend event to the command awaiting it, or emits it with the
+/// id of `pending_request`.
+fn emit_to_request<R: Runtime, T>(
+    app_handle: &AppHandle<R>,
+    pending_request: Option<PendingRequest>,
+    event_name: &str,
+    r: Result<T, BackendError>,
+) where
+    T: serde::Serialize + Clone + std::fmt::Debug,
+{
+    let request_id = pending_request.as_ref().map(|request| request.request_id);
+    let result = r.map_err(|e| {
+        let error = AppError::from(e).with_event(event_name);
+        match &pending_request {
+            Some(request) => error.with_context(request.wallet_id, request.account_id),
+            None => error,
+        }
+    });
+
+    if let Some(responder) = pending_request.and_then(|request| request.responder) {
+        // The command may have stopped waiting, there is nobody else to tell then
+        let _ = responder.send(result.and_then(to_response_value));
+        return;
+    }
+
+    match result {
+        Ok(data) => {
+            app_handle
+                .emit(event_name, ResponseEvent::new(request_id, data))
+                .expect("Failed to emit backend event");
+        }
+        Err(error) => emit_error(app_handle, request_id, error),
+    }
+}
+
+fn to_response_value<T: serde::Serialize>(data: T) -> Result<serde_json::Value, AppError> {
+    serde_json::to_value(data).map_err(|e| AppError::new(ErrorCode::Backend, e.to_string()))
+}
+
+fn emit_error<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    request_id: Option<RequestId>,
+    error: AppError,
+) {
+    app_handle
+        .emit("Error", ErrorEvent::new(request_id, error))
+        .expect("Failed to emit backend event");
+}
+
+fn register_wallet<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    wallet_registry: &mut WalletRegistry,
+    event_name: &str,
+    msg: &Result<WalletInfo, BackendError>,
+) {
+    if let Ok(wallet_info) = msg {
+        if let Err(e) = wallet_registry.add_wallet(wallet_info) {
+            // The wallet is open anyway, only the recent wallets list could not be saved
+            emit_error(app_handle, None, e.with_event(event_name));
         }
     }
 }
 
-fn process_event(app_handle: &AppHandle, event: BackendEvent, chain_config: &ChainConfig) {
+fn process_event<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    event: BackendEvent,
+    chain_config: &ChainConfig,
+    event_state: &mut EventState,
+    low_priority: bool,
+) {
+    let EventState {
+        request_tracker,
+        event_cache,
+        wallet_registry,
+        token_infos,
+        utxos,
+        histories,
+        // Transactions never change, whatever the event
+        transactions: _,
+    } = event_state;
+
     match event {
         BackendEvent::P2p(msg) => {
             emit_event_or_error(app_handle, "P2p", Ok(msg));
         }
         BackendEvent::ChainInfo(msg) => {
+            event_cache.set_chain_info(msg.clone());
             emit_event_or_error(app_handle, "ChainInfo", Ok(msg));
         }
         BackendEvent::Balance(wallet_id, account_id, balance) => {
-            let balance = BalanceResult::new(wallet_id, account_id, balance);
+            utxos.remove_account(wallet_id, account_id);
+            let token_ids = token_infos.start_lookups(&balance);
+            if !token_ids.is_empty() {
+                tauri::async_runtime::spawn(lookup_balance_tokens(
+                    app_handle.clone(),
+                    wallet_id,
+                    account_id,
+                    token_ids,
+                ));
+            }
+            let known_tokens = token_infos.for_balance(&balance);
+            let balance = BalanceResult::new(wallet_id, account_id, balance, known_tokens);
+            event_cache.set_balance(wallet_id, account_id, balance.clone());
             emit_event_or_error(app_handle, "Balance", Ok(balance));
         }
         BackendEvent::StakingBalance(wallet_id, account_id, staking_balance) => {
             let staking_balance = StakingBalanceResult::new(wallet_id, account_id, staking_balance);
+            event_cache.set_staking_balance(wallet_id, account_id, staking_balance.clone());
             emit_event_or_error(app_handle, "StakingBalance", Ok(staking_balance));
         }
         BackendEvent::WalletBestBlock(wallet_id, block_info) => {
             let wallet_best_block = WalletBestBlockResult::new(wallet_id, block_info);
+            event_cache.set_wallet_best_block(wallet_id, wallet_best_block.clone());
             emit_event_or_error(app_handle, "WalletBestBlock", Ok(wallet_best_block));
         }
         BackendEvent::ImportWallet(msg) => {
-            emit_event_or_error(app_handle, "ImportWallet", msg);
+            register_wallet(app_handle, wallet_registry, "ImportWallet", &msg);
+            emit_response_or_error(app_handle, request_tracker, "ImportWallet", msg);
         }
         BackendEvent::OpenWallet(msg) => {
-            emit_event_or_error(app_handle, "OpenWallet", msg);
+            register_wallet(app_handle, wallet_registry, "OpenWallet", &msg);
+            emit_response_or_error(app_handle, request_tracker, "OpenWallet", msg);
         }
         BackendEvent::NewAddress(msg) => {
-            emit_event_or_error(app_handle, "NewAddress", msg);
+            emit_response_or_error(app_handle, request_tracker, "NewAddress", msg);
         }
         BackendEvent::UpdateEncryption(msg) => {
-            emit_event_or_error(app_handle, "UpdateEncryption", msg);
+            if let Ok((wallet_id, encryption)) = &msg {
+                wallet_registry.set_encryption(*wallet_id, *encryption);
+            }
+            let wallet_id = msg.as_ref().ok().map(|(wallet_id, _)| *wallet_id);
+            emit_wallet_response_or_error(
+                app_handle,
+                request_tracker,
+                "UpdateEncryption",
+                wallet_id,
+                None,
+                msg,
+            );
         }
         BackendEvent::CloseWallet(msg) => {
-            emit_event_or_error(app_handle, "CloseWallet", Ok(msg));
+            event_cache.remove_wallet(msg);
+            wallet_registry.remove_wallet(msg);
+            utxos.remove_wallet(msg);
+            histories.remove_wallet(msg);
+            emit_wallet_response_or_error(
+                app_handle,
+                request_tracker,
+                "CloseWallet",
+                Some(msg),
+                None,
+                Ok(msg),
+            );
         }
         BackendEvent::NewAccount(msg) => {
-            emit_event_or_error(app_handle, "NewAccount", msg);
+            if let Ok((wallet_id, _, _)) = &msg {
+                wallet_registry.add_account(*wallet_id);
+            }
+            let wallet_id = msg.as_ref().ok().map(|(wallet_id, _, _)| *wallet_id);
+            emit_wallet_response_or_error(
+                app_handle,
+                request_tracker,
+                "NewAccount",
+                wallet_id,
+                None,
+                msg,
+            );
         }
         BackendEvent::ToggleStaking(msg) => {
-            emit_event_or_error(app_handle, "ToggleStaking", msg);
+            emit_response_or_error(app_handle, request_tracker, "ToggleStaking", msg);
+        }
+        BackendEvent::ConsoleResponse(wallet_id, account_id, result) => {
+            emit_wallet_response_or_error(
+                app_handle,
+                request_tracker,
+                "ConsoleResponse",
+                Some(wallet_id),
+                Some(account_id),
+                result,
+            );
         }
-        BackendEvent::ConsoleResponse(_, _, result) => match result {
-            Ok(console_result) => {
-                app_handle
-                    .emit("ConsoleResponse", console_result)
-                    .expect("Failed to emit backend event");
-            }
-            Err(e) => {
-                app_handle
-                    .emit("ConsoleResponse", e.to_string())
-                    .expect("Failed to emit backend event");
-            }
-        },
         BackendEvent::Broadcast(msg) => {
-            emit_event_or_error(app_handle, "Broadcast", msg);
+            let wallet_id = msg.as_ref().ok().copied();
+            emit_wallet_response_or_error(
+                app_handle,
+                request_tracker,
+                "Broadcast",
+                wallet_id,
+                None,
+                msg,
+            );
         }
         BackendEvent::SendAmount(msg) => {
             let result = msg.and_then(|transaction_info| {
                 TransactionResult::from_transaction_info(chain_config, transaction_info)
             });
-            emit_event_or_error(app_handle, "SendAmount", result);
+            emit_response_or_error(app_handle, request_tracker, "SendAmount", result);
         }
         BackendEvent::StakeAmount(msg) => {
             let result = msg.and_then(|transaction_info| {
                 TransactionResult::from_transaction_info(chain_config, transaction_info)
             });
-            emit_event_or_error(app_handle, "StakeAmount", result);
+            emit_response_or_error(app_handle, request_tracker, "StakeAmount", result);
         }
         BackendEvent::DecommissionPool(msg) => {
             let result = msg.and_then(|transaction_info| {
                 TransactionResult::from_transaction_info(chain_config, transaction_info)
             });
-            emit_event_or_error(app_handle, "DecommissionPool", result);
+            emit_response_or_error(app_handle, request_tracker, "DecommissionPool", result);
         }
         BackendEvent::CreateDelegation(msg) => {
             let result = msg.and_then(|transaction_info| {
                 TransactionResult::from_transaction_info(chain_config, transaction_info)
             });
-            emit_event_or_error(app_handle, "CreateDelegation", result);
+            emit_response_or_error(app_handle, request_tracker, "CreateDelegation", result);
         }
         BackendEvent::DelegateStaking(msg) => {
             let result = msg.and_then(|transaction_info| {
@@ -233,13 +481,18 @@ fn process_event(app_handle: &AppHandle, event: BackendEvent, chain_config: &Cha
                     )
                 })
             });
-            emit_event_or_error(app_handle, "DelegateStaking", result);
+            emit_response_or_error(app_handle, request_tracker, "DelegateStaking", result);
         }
         BackendEvent::SendDelegationToAddress(msg) => {
             let result = msg.and_then(|transaction_info| {
                 TransactionResult::from_transaction_info(chain_config, transaction_info)
             });
-            emit_event_or_error(app_handle, "SendDelegationToAddress", result);
+            emit_response_or_error(
+                app_handle,
+                request_tracker,
+                "SendDelegationToAddress",
+                result,
+            );
         }
         BackendEvent::DelegationsBalance(wallet_id, account_id, delegations_balance) => {
             let delegations_balance = delegations_balance
@@ -255,32 +508,533 @@ fn process_event(app_handle: &AppHandle, event: BackendEvent, chain_config: &Cha
 
             let delegations_balance =
                 DelegationsBalanceResult::new(wallet_id, account_id, delegations_balance);
+            event_cache.set_delegations_balance(wallet_id, account_id, delegations_balance.clone());
 
             emit_event_or_error(app_handle, "DelegationBalance", Ok(delegations_balance));
         }
         BackendEvent::TransactionList(wallet_id, account_id, msg) => {
-            let result = msg.map(|transaction_list| {
-                TransactionListResult::new(wallet_id, account_id, transaction_list)
-            });
+            let result = msg
+                .map(|transaction_list| {
+                    TransactionListResult::new(wallet_id, account_id, transaction_list)
+                })
+                .map_err(|e| AppError::from(e).with_context(Some(wallet_id), Some(account_id)));
+
+            // Answers to requests come on the high priority channel, the low priority one
+            // carries the refreshes sent when the transactions of the account change
+            if low_priority {
+                histories.remove_account(wallet_id, account_id);
+            } else {
+                // Pages fetched by `transaction_history` go back to it, not to the UI
+                let pending_request =
+                    request_tracker.resolve_for("TransactionList", wallet_id, account_id);
+                if let Some(responder) = pending_request.and_then(|request| request.responder) {
+                    let _ = responder.send(result.and_then(to_response_value));
+                    return;
+                }
+            }
+
+            if let Ok(transaction_list) = &result {
+                event_cache.set_transaction_list(wallet_id, account_id, transaction_list.clone());
+            }
             emit_event_or_error(app_handle, "TransactionList", result);
         }
     }
 }
 
+/// Looks up the tokens a balance holds which are not known yet, then emits the balance
+/// again with their metadata.
+async fn lookup_balance_tokens<R: Runtime>(
+    app_handle: AppHandle<R>,
+    wallet_id: WalletId,
+    account_id: AccountId,
+    token_ids: Vec<String>,
+) {
+    let state = app_handle.state::<Mutex<AppState>>();
+    for token_id in &token_ids {
+        if let Err(e) = token_info(&state, wallet_id, account_id, token_id).await {
+            emit_error(&app_handle, None, e);
+        }
+    }
+
+    let state = state.lock().await;
+    let mut event_state = state.event_state.lock().await;
+    let EventState {
+        event_cache,
+        token_infos,
+        ..
+    } = &mut *event_state;
+    if let Some(balance) = event_cache.balance_mut(wallet_id, account_id) {
+        balance.update_token_infos(token_infos);
+        emit_event_or_error(&app_handle, "Balance", Ok(balance.clone()));
+    }
+}
+
+/// Sends `request` to the backend and returns the id that will be echoed in `response_event`.
+async fn send_request(
+    state: &AppState,
+    response_event: &'static str,
+    wallet_id: Option<WalletId>,
+    account_id: Option<AccountId>,
+    request: BackendRequest,
+) -> Result<RequestId, AppError> {
+    let backend_sender = state.backend_sender()?;
+
+    let request_id = state.event_state.lock().await.request_tracker.register(
+        response_event,
+        wallet_id,
+        account_id,
+    );
+
+    backend_sender.send(request);
+
+    Ok(request_id)
+}
+
+/// Runs a wallet console command and returns what it printed.
+async fn console_command(
+    state: &Mutex<AppState>,
+    wallet_id: WalletId,
+    account_id: AccountId,
+    command: String,
+) -> Result<String, AppError> {
+    let response = send_request_and_wait(
+        state,
+        "ConsoleResponse",
+        Some(wallet_id),
+        Some(account_id),
+        BackendRequest::ConsoleCommand {
+            wallet_id,
+            account_id,
+            command,
+        },
+    )
+    .await?;
+
+    response
+        .get("Print")
+        .and_then(serde_json::Value::as_str)
+        .map(str::to_owned)
+        .ok_or_else(|| {
+            AppError::new(
+                ErrorCode::Wallet,
+                format!("Unexpected console response: {response}"),
+            )
+        })
+}
+
+/// Lists the spendable outputs of an account, as printed by the `account-utxos` command.
+async fn account_utxos(
+    state: &Mutex<AppState>,
+    wallet_id: WalletId,
+    account_id: AccountId,
+) -> Result<Vec<UtxoEntry>, AppError> {
+    account_utxos_by_lock(state, wallet_id, account_id, false).await
+}
+
+/// Lists either the spendable or the locked unspent outputs of an account.
+async fn account_utxos_by_lock(
+    state: &Mutex<AppState>,
+    wallet_id: WalletId,
+    account_id: AccountId,
+    locked: bool,
+) -> Result<Vec<UtxoEntry>, AppError> {
+    let with_locked = if locked { "locked" } else { "unlocked" };
+    let output = console_command(
+        state,
+        wallet_id,
+        account_id,
+        console::command_line("account-utxos", &["all", with_locked]),
+    )
+    .await?;
+    let utxos = serde_json::from_str::<Vec<serde_json::Value>>(&output)
+        .map_err(|e| AppError::new(ErrorCode::Wallet, format!("Unexpected UTXO list: {e}")))?;
+    utxos.iter().map(|utxo| UtxoEntry::from_json(utxo, locked)).collect()
+}
+
+/// Runs a console command that builds a transaction and returns the transaction without
+/// submitting it, so that it goes through the same confirmation as the backend ones.
+///
+/// Broadcasting is always turned off for the command and the user's setting restored
+/// afterwards, with the console of every wallet locked meanwhile so that no other command
+/// runs in between. A failed restore does not lose the transaction, it is returned along
+/// with it.
+async fn console_transaction(
+    state: &Mutex<AppState>,
+    wallet_id: WalletId,
+    account_id: AccountId,
+    command: String,
+) -> Result<TransactionResult, AppError> {
+    let console_broadcast = state.lock().await.console_broadcast.clone();
+    let console_broadcast = console_broadcast.lock().await;
+
+    let set_broadcast = |enabled: &str| {
+        console_command(
+            state,
+            wallet_id,
+            account_id,
+            console::command_line("config-broadcast", &[enabled]),
+        )
+    };
+
+    set_broadcast("no").await?;
+    let output = console_command(state, wallet_id, account_id, command).await;
+    let restored = set_broadcast(console_broadcast.restore_arg(wallet_id)).await;
+    drop(console_broadcast);
+
+    let (_, transaction) = console::printed_data::<SignedTransaction>(&output?)?;
+    let state = state.lock().await;
+    let transaction = transaction_result(state.chain_config()?, wallet_id, transaction)?;
+    match restored {
+        Ok(_) => Ok(transaction),
+        Err(error) => Ok(transaction.with_broadcast_error(error)),
+    }
+}
+
+/// Builds and signs a transaction spending `utxos` through the `transaction-compose`
+/// and `account-sign-raw-transaction` console commands, nothing is broadcast.
+///
+/// The console adds neither change nor fee, so the fee is priced from the size of the
+/// signed transaction at `fee_rate` and the transaction composed again until it pays
+/// enough.
+async fn compose_transaction(
+    state: &Mutex<AppState>,
+    wallet_id: WalletId,
+    account_id: AccountId,
+    payment: Payment,
+    utxos: &[UtxoEntry],
+    spend_all: bool,
+    fee_rate: &FeeRate,
+) -> Result<PreparedTransaction, AppError> {
+    let address = payment
+        .first_address()
+        .ok_or_else(|| AppError::invalid_input("No outputs given"))?;
+    let coin_decimals = state.lock().await.chain_config()?.coin_decimals();
+    let quote = mempool_fee(state, wallet_id, account_id, address, utxos)
+        .await?
+        .at_rate(fee_rate, coin_decimals)?;
+
+    let mut fee = Amount::ZERO;
+    for _ in 0..MAX_FEE_ROUNDS {
+        let draft = Draft::new(&payment, utxos, spend_all, fee)?;
+        let args = draft.compose_args(coin_decimals)?;
+        let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+        let output = console_command(
+            state,
+            wallet_id,
+            account_id,
+            console::command_line("transaction-compose", &args),
+        )
+        .await?;
+        let (composed, _) = console::printed_data::<PartiallySignedTransaction>(&output)?;
+
+        let output = console_command(
+            state,
+            wallet_id,
+            account_id,
+            console::command_line("account-sign-raw-transaction", &[&composed]),
+        )
+        .await?;
+        let (_, signed) = console::printed_data::<SignedTransaction>(&output).map_err(|_| {
+            AppError::new(
+                ErrorCode::Wallet,
+                "The account cannot sign every input of the transaction",
+            )
+        })?;
+
+        if !draft.matches(signed.transaction()) {
+            return Err(AppError::new(
+                ErrorCode::Wallet,
+                "The signed transaction differs from the composed one",
+            ));
+        }
+
+        fee = quote.fee_for(signed.encoded_size())?;
+        if draft.fee() >= fee {
+            let state = state.lock().await;
+            let transaction = transaction_result(state.chain_config()?, wallet_id, signed)?;
+            return Ok(PreparedTransaction::new(transaction, draft.estimate()?));
+        }
+    }
+
+    Err(AppError::new(
+        ErrorCode::Wallet,
+        "Could not settle the fee of the transaction",
+    ))
+}
+
+/// Prices transactions the way the backend does, by having it build a 1 atom send to
+/// `address` which is never submitted.
+async fn mempool_fee(
+    state: &Mutex<AppState>,
+    wallet_id: WalletId,
+    account_id: AccountId,
+    address: &str,
+    utxos: &[UtxoEntry],
+) -> Result<FeeQuote, AppError> {
+    let coin_decimals = state.lock().await.chain_config()?.coin_decimals();
+    let transaction = send_request_and_wait(
+        state,
+        "SendAmount",
+        Some(wallet_id),
+        Some(account_id),
+        BackendRequest::SendAmount(SendRequest {
+            wallet_id,
+            account_id,
+            amount: Amount::from_atoms(1).into_fixedpoint_str(coin_decimals),
+            address: address.to_owned(),
+        }),
+    )
+    .await?;
+
+    let fee = FeeEstimate::new(&transaction, utxos, address)?.fee();
+    let info = transaction
+        .get("transaction_info")
+        .cloned()
+        .and_then(|info| serde_json::from_value::<TransactionInfo>(info).ok())
+        .ok_or_else(|| {
+            AppError::new(
+                ErrorCode::Backend,
+                format!("Unexpected transaction: {transaction}"),
+            )
+        })?;
+    Ok(FeeQuote::new(fee, info.tx.take_tx().encoded_size()))
+}
+
+/// What the backend would have returned for a transaction it built itself, which is
+/// what `submit_transaction_wrapper` takes.
+fn transaction_result(
+    chain_config: &ChainConfig,
+    wallet_id: WalletId,
+    transaction: SignedTransaction,
+) -> Result<TransactionResult, AppError> {
+    let transaction_info = TransactionInfo {
+        wallet_id,
+        tx: SignedTransactionWrapper::new(transaction),
+    };
+    TransactionResult::from_transaction_info(chain_config, transaction_info).map_err(AppError::from)
+}
+
+/// Decodes a hex encoded transaction, failing for unsigned and partially signed ones.
+fn decode_signed_transaction(hex: &str) -> Result<SignedTransaction, AppError> {
+    let data = hex::decode(hex.trim())
+        .map_err(|e| AppError::invalid_input(format!("Invalid hex: {e}")))?;
+    SignedTransaction::decode_all(&mut data.as_slice())
+        .map_err(|e| AppError::invalid_input(format!("Not a signed transaction: {e}")))
+}
+
+/// Describes a transaction moving between the hot and the cold side, using the
+/// `transaction-inspect` console command for the human readable summary.
+async fn inspect_transaction_file(
+    state: &Mutex<AppState>,
+    wallet_id: WalletId,
+    account_id: AccountId,
+    transaction: &str,
+) -> Result<TransactionFileResult, AppError> {
+    let summary = console_command(
+        state,
+        wallet_id,
+        account_id,
+        console::command_line("transaction-inspect", &[transaction]),
+    )
+    .await?;
+
+    // Unsigned and partially signed transactions do not decode as signed ones
+    let state = state.lock().await;
+    let signed = match decode_signed_transaction(transaction) {
+        Ok(signed) => Some(transaction_result(
+            state.chain_config()?,
+            wallet_id,
+            signed,
+        )?),
+        Err(_) => None,
+    };
+
+    Ok(TransactionFileResult::new(
+        transaction.to_owned(),
+        summary,
+        signed,
+    ))
+}
+
+/// Returns the info of any kind of token as printed by the `token-info` console command.
+async fn lookup_token(
+    state: &Mutex<AppState>,
+    wallet_id: WalletId,
+    account_id: AccountId,
+    token_id: &str,
+) -> Result<serde_json::Value, AppError> {
+    let output = console_command(
+        state,
+        wallet_id,
+        account_id,
+        console::command_line("token-info", &[token_id]),
+    )
+    .await?;
+    serde_json::from_str(&output)
+        .map_err(|e| AppError::new(ErrorCode::Wallet, format!("Unexpected token info: {e}")))
+}
+
+/// Returns the info of a fungible token, looking it up through the console the first time.
+async fn token_info(
+    state: &Mutex<AppState>,
+    wallet_id: WalletId,
+    account_id: AccountId,
+    token_id: &str,
+) -> Result<TokenInfo, AppError> {
+    {
+        let state = state.lock().await;
+        if let Some(info) = state.event_state.lock().await.token_infos.get(token_id) {
+            return Ok(info.clone());
+        }
+    }
+
+    let info = lookup_token(state, wallet_id, account_id, token_id).await?;
+    let info = TokenInfo::from_json(token_id, &info)?;
+
+    let state = state.lock().await;
+    state.event_state.lock().await.token_infos.insert(info.clone());
+    Ok(info)
+}
+
+/// Sends a request and waits for its response instead of having it emitted, for commands
+/// that need the results of several backend requests.
+async fn send_request_and_wait(
+    state: &Mutex<AppState>,
+    response_event: &'static str,
+    wallet_id: Option<WalletId>,
+    account_id: Option<AccountId>,
+    request: BackendRequest,
+) -> Result<serde_json::Value, AppError> {
+    let receiver = {
+        let state = state.lock().await;
+        let backend_sender = state.backend_sender()?;
+        let receiver = state.event_state.lock().await.request_tracker.register_awaited(
+            response_event,
+            wallet_id,
+            account_id,
+        );
+        backend_sender.send(request);
+        receiver
+    };
+
+    // The pending request is dropped without an answer if the node stops meanwhile
+    receiver.await.map_err(|_| AppError::node_not_initialized())?
+}
+
+#[tauri::command]
+pub async fn list_open_wallets(
+    state: State<'_, Mutex<AppState>>,
+) -> Result<Vec<OpenWalletEntry>, AppError> {
+    let state = state.lock().await;
+    let event_state = state.event_state.lock().await;
+    Ok(event_state.wallet_registry.open_wallets())
+}
+
+/// Records which of the open wallets is currently shown in the UI.
+#[tauri::command]
+pub async fn select_wallet(
+    state: State<'_, Mutex<AppState>>,
+    wallet_id: WalletId,
+) -> Result<(), AppError> {
+    let state = state.lock().await;
+    let mut event_state = state.event_state.lock().await;
+    event_state.wallet_registry.select_wallet(wallet_id)
+}
+
+#[tauri::command]
+pub async fn list_recent_wallets(
+    state: State<'_, Mutex<AppState>>,
+) -> Result<Vec<RecentWallet>, AppError> {
+    let state = state.lock().await;
+    let event_state = state.event_state.lock().await;
+    Ok(event_state.wallet_registry.recent_wallets().to_vec())
+}
+
+#[tauri::command]
+pub async fn remove_recent_wallet(
+    state: State<'_, Mutex<AppState>>,
+    file_path: String,
+) -> Result<(), AppError> {
+    let state = state.lock().await;
+    let mut event_state = state.event_state.lock().await;
+    event_state.wallet_registry.remove_recent_wallet(&PathBuf::from(file_path))
+}
+
+/// Contacts saved for the network the node is running on.
+#[tauri::command]
+pub async fn list_contacts(state: State<'_, Mutex<AppState>>) -> Result<Vec<Contact>, AppError> {
+    let state = state.lock().await;
+    Ok(state.address_book.contacts(state.network()?).to_vec())
+}
+
+/// Contacts matching what has been typed so far in an address field.
+#[tauri::command]
+pub async fn find_contacts(
+    state: State<'_, Mutex<AppState>>,
+    query: String,
+) -> Result<Vec<Contact>, AppError> {
+    let state = state.lock().await;
+    Ok(state.address_book.find(state.network()?, &query))
+}
+
+#[tauri::command]
+pub async fn add_contact(
+    state: State<'_, Mutex<AppState>>,
+    label: String,
+    address: String,
+) -> Result<(), AppError> {
+    let mut state = state.lock().await;
+    let network = state.network()?;
+    let chain_config = state.chain_config()?.clone();
+    state.address_book.add_contact(network, &chain_config, label, address)
+}
+
+#[tauri::command]
+pub async fn update_contact(
+    state: State<'_, Mutex<AppState>>,
+    address: String,
+    contact: Contact,
+) -> Result<(), AppError> {
+    let mut state = state.lock().await;
+    let network = state.network()?;
+    let chain_config = state.chain_config()?.clone();
+    state.address_book.update_contact(network, &chain_config, &address, contact)
+}
+
+#[tauri::command]
+pub async fn delete_contact(
+    state: State<'_, Mutex<AppState>>,
+    address: String,
+) -> Result<(), AppError> {
+    let mut state = state.lock().await;
+    let network = state.network()?;
+    state.address_book.delete_contact(network, &address)
+}
+
+#[tauri::command]
+pub fn generate_mnemonic() -> String {
+    mnemonic::generate()
+}
+
+#[tauri::command]
+pub fn validate_mnemonic(mnemonic: &str) -> MnemonicValidation {
+    mnemonic::validate(mnemonic)
+}
+
 #[tauri::command]
 pub async fn add_create_wallet_wrapper(
     state: tauri::State<'_, Mutex<AppState>>,
     request: OpenCreateWalletRequest,
-) -> Result<(), String> {
+) -> Result<RequestId, AppError> {
     let mnemonic = wallet_controller::mnemonic::Mnemonic::parse(request.mnemonic)
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| AppError::invalid_input(e.to_string()))?;
 
     let file_path = PathBuf::from(request.file_path);
 
     let wallet_type = match request.wallet_type.as_str() {
         "Hot" => WalletType::Hot,
         "Cold" => WalletType::Cold,
-        _ => return Err("Invalid wallet type".to_owned()),
+        _ => return Err(AppError::invalid_input("Invalid wallet type")),
     };
 
     let import = match request.import {
@@ -290,50 +1044,134 @@ pub async fn add_create_wallet_wrapper(
 
     let state = state.lock().await;
 
-    state.backend_sender.as_ref().expect("Backend sender must be initialized").send(
+    // The backend recovers wallets without a passphrase, so the wallet file is written
+    // here and the backend only opens it; the UI awaits "OpenWallet" then
+    if let Some(passphrase) = request.passphrase.filter(|passphrase| !passphrase.is_empty()) {
+        let best_block = match import {
+            ImportOrCreate::Import => None,
+            ImportOrCreate::Create => {
+                let event_state = state.event_state.lock().await;
+                let best_block = event_state.event_cache.best_block().ok_or_else(|| {
+                    AppError::new(
+                        ErrorCode::NodeBusy,
+                        "The node has not reported its chain yet",
+                    )
+                })?;
+                Some(best_block)
+            }
+        };
+        mnemonic::create_wallet_file(
+            state.chain_config()?.clone(),
+            &file_path,
+            mnemonic,
+            &passphrase,
+            wallet_type,
+            best_block,
+        )?;
+
+        return send_request(
+            &state,
+            "OpenWallet",
+            None,
+            None,
+            BackendRequest::OpenWallet {
+                file_path,
+                wallet_type,
+            },
+        )
+        .await;
+    }
+
+    let request_id = send_request(
+        &state,
+        "ImportWallet",
+        None,
+        None,
         BackendRequest::RecoverWallet {
             file_path,
             wallet_type,
             mnemonic,
             import,
         },
-    );
+    )
+    .await?;
 
-    Ok(())
+    Ok(request_id)
 }
 
 #[tauri::command]
 pub async fn add_open_wallet_wrapper(
     state: tauri::State<'_, Mutex<AppState>>,
     request: OpenWalletRequest,
-) -> Result<(), String> {
+) -> Result<RequestId, AppError> {
     let file_path = PathBuf::from(request.file_path);
 
     let wallet_type = match request.wallet_type.as_str() {
         "Hot" => WalletType::Hot,
         "Cold" => WalletType::Cold,
-        _ => return Err("Invalid wallet type".to_owned()),
+        _ => return Err(AppError::invalid_input("Invalid wallet type")),
     };
 
-    {
-        let state = state.lock().await;
+    let state = state.lock().await;
 
-        state.backend_sender.as_ref().expect("Backend sender must be initialized").send(
-            BackendRequest::OpenWallet {
-                file_path,
-                wallet_type,
-            },
-        );
+    let request_id = send_request(
+        &state,
+        "OpenWallet",
+        None,
+        None,
+        BackendRequest::OpenWallet {
+            file_path,
+            wallet_type,
+        },
+    )
+    .await?;
+
+    Ok(request_id)
+}
+
+#[tauri::command]
+pub async fn list_utxos(
+    state: State<'_, Mutex<AppState>>,
+    wallet_id: WalletId,
+    account_id: AccountId,
+) -> Result<Vec<UtxoEntry>, AppError> {
+    let mut utxos = account_utxos_by_lock(&state, wallet_id, account_id, false).await?;
+    utxos.extend(account_utxos_by_lock(&state, wallet_id, account_id, true).await?);
+
+    let history = transaction_history(&state, wallet_id, account_id).await?;
+    let best_height = {
+        let state = state.lock().await;
+        let event_state = state.event_state.lock().await;
+        event_state.event_cache.wallet_best_block_height(wallet_id)
+    };
+    if let Some(best_height) = best_height {
+        utxo::set_confirmations(&mut utxos, &history, best_height.into_int());
     }
 
-    Ok(())
+    Ok(utxos)
 }
 
+/// Builds and signs the transaction `send_amount_wrapper` would, without submitting it,
+/// and reports what sending would cost. The transaction is the one to submit once the
+/// user has confirmed the estimate.
 #[tauri::command]
-pub async fn send_amount_wrapper(
+pub async fn estimate_fee(
+    state: State<'_, Mutex<AppState>>,
+    request: SendAmountRequest,
+) -> Result<PreparedTransaction, AppError> {
+    compose_send(&state, request).await
+}
+
+#[tauri::command]
+pub async fn send_amount_wrapper<R: Runtime>(
+    app_handle: AppHandle<R>,
     state: tauri::State<'_, Mutex<AppState>>,
     request: SendAmountRequest,
-) -> Result<(), String> {
+) -> Result<RequestId, AppError> {
+    if !request.inputs.is_empty() || request.fee_rate != FeeRate::Normal {
+        return send_composed(app_handle, &state, request).await;
+    }
+
     let state = state.lock().await;
 
     let request = SendRequest {
@@ -343,34 +1181,125 @@ pub async fn send_amount_wrapper(
         address: request.address,
     };
 
-    state
-        .backend_sender
-        .as_ref()
-        .expect("Backend sender must be initialized")
-        .send(BackendRequest::SendAmount(request));
+    let request_id = send_request(
+        &state,
+        "SendAmount",
+        Some(request.wallet_id),
+        Some(request.account_id),
+        BackendRequest::SendAmount(request),
+    )
+    .await?;
+
+    Ok(request_id)
+}
+
+/// Sends through the wallet console, as the backend request always lets the wallet pick
+/// the inputs and pays the mempool rate. The transaction is emitted as the "SendAmount"
+/// response, like the one built by the backend.
+async fn send_composed<R: Runtime>(
+    app_handle: AppHandle<R>,
+    state: &Mutex<AppState>,
+    request: SendAmountRequest,
+) -> Result<RequestId, AppError> {
+    let (wallet_id, account_id) = (request.wallet_id, request.account_id);
+    let request_id = {
+        let state = state.lock().await;
+        state.backend_sender()?;
+        let mut event_state = state.event_state.lock().await;
+        event_state.request_tracker.allocate()
+    };
+
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<Mutex<AppState>>();
+        match compose_send(&state, request).await {
+            Ok(prepared) => {
+                app_handle
+                    .emit(
+                        "SendAmount",
+                        ResponseEvent::new(Some(request_id), prepared.transaction()),
+                    )
+                    .expect("Failed to emit backend event");
+            }
+            Err(error) => emit_error(
+                &app_handle,
+                Some(request_id),
+                error.with_event("SendAmount").with_context(Some(wallet_id), Some(account_id)),
+            ),
+        }
+    });
+
+    Ok(request_id)
+}
+
+/// Builds a send through the wallet console, from the chosen outputs if any.
+async fn compose_send(
+    state: &Mutex<AppState>,
+    request: SendAmountRequest,
+) -> Result<PreparedTransaction, AppError> {
+    let SendAmountRequest {
+        wallet_id,
+        account_id,
+        amount,
+        address,
+        inputs,
+        fee_rate,
+    } = request;
+
+    let outputs = {
+        let state = state.lock().await;
+        payout::validate_outputs(
+            state.chain_config()?,
+            &[PaymentOutput {
+                address,
+                amount,
+                token: None,
+            }],
+        )?
+    };
+
+    let utxos = account_utxos(state, wallet_id, account_id).await?;
+    let (utxos, spend_all) = if inputs.is_empty() {
+        (utxos, false)
+    } else {
+        (utxo::chosen(&utxos, &inputs)?, true)
+    };
 
-    Ok(())
+    compose_transaction(
+        state,
+        wallet_id,
+        account_id,
+        Payment::Outputs(outputs),
+        &utxos,
+        spend_all,
+        &fee_rate,
+    )
+    .await
 }
 
 #[tauri::command]
 pub async fn new_address_wrapper(
     state: tauri::State<'_, Mutex<AppState>>,
     request: NewAddressRequest,
-) -> Result<(), String> {
+) -> Result<RequestId, AppError> {
     let state = state.lock().await;
 
-    state.backend_sender.as_ref().expect("Backend sender must be initialized").send(
+    let request_id = send_request(
+        &state,
+        "NewAddress",
+        Some(request.wallet_id),
+        Some(request.account_id),
         BackendRequest::NewAddress(request.wallet_id, request.account_id),
-    );
+    )
+    .await?;
 
-    Ok(())
+    Ok(request_id)
 }
 
 #[tauri::command]
 pub async fn update_encryption_wrapper(
     state: tauri::State<'_, Mutex<AppState>>,
     request: UpdateEncryptionRequest,
-) -> Result<(), String> {
+) -> Result<RequestId, AppError> {
     let state = state.lock().await;
 
     let update_encryption_action = match request.action.to_lowercase().as_str() {
@@ -378,7 +1307,7 @@ pub async fn update_encryption_wrapper(
             if let Some(pass) = request.password {
                 EncryptionAction::SetPassword(pass.to_string())
             } else {
-                return Err("Password cannot be empty".to_string());
+                return Err(AppError::invalid_input("Password cannot be empty"));
             }
         }
         "remove_password" => EncryptionAction::RemovePassword,
@@ -386,44 +1315,427 @@ pub async fn update_encryption_wrapper(
             if let Some(pass) = request.password {
                 EncryptionAction::Unlock(pass.to_string())
             } else {
-                return Err("Password cannot be empty".to_string());
+                return Err(AppError::invalid_input("Password cannot be empty"));
             }
         }
         "lock" => EncryptionAction::Lock,
-        _ => return Err("Invalid encryption action".to_owned()),
+        _ => return Err(AppError::invalid_input("Invalid encryption action")),
     };
 
-    state.backend_sender.as_ref().expect("Backend sender must be initialized").send(
+    let request_id = send_request(
+        &state,
+        "UpdateEncryption",
+        Some(request.wallet_id),
+        None,
         BackendRequest::UpdateEncryption {
             wallet_id: request.wallet_id,
             action: update_encryption_action,
         },
-    );
+    )
+    .await?;
 
-    Ok(())
+    Ok(request_id)
 }
 
 #[tauri::command]
 pub async fn close_wallet_wrapper(
     state: tauri::State<'_, Mutex<AppState>>,
     wallet_id: WalletId,
-) -> Result<(), String> {
+) -> Result<RequestId, AppError> {
+    let state = state.lock().await;
+
+    let request_id = send_request(
+        &state,
+        "CloseWallet",
+        Some(wallet_id),
+        None,
+        BackendRequest::CloseWallet(wallet_id),
+    )
+    .await?;
+
+    Ok(request_id)
+}
+
+#[tauri::command]
+pub async fn get_token_info(
+    state: State<'_, Mutex<AppState>>,
+    wallet_id: WalletId,
+    account_id: AccountId,
+    token_id: String,
+) -> Result<TokenInfo, AppError> {
+    token_info(&state, wallet_id, account_id, &token_id).await
+}
+
+/// Builds a token transfer, returned unsubmitted like the other transactions.
+#[tauri::command]
+pub async fn send_tokens(
+    state: State<'_, Mutex<AppState>>,
+    request: SendTokenRequest,
+) -> Result<TransactionResult, AppError> {
+    let info = token_info(
+        &state,
+        request.wallet_id,
+        request.account_id,
+        &request.token_id,
+    )
+    .await?;
+    info.parse_amount(&request.amount)?;
+    Address::<Destination>::from_string(state.lock().await.chain_config()?, &request.address)
+        .map_err(|e| AppError::new(ErrorCode::InvalidAddress, e.to_string()))?;
+
+    console_transaction(
+        &state,
+        request.wallet_id,
+        request.account_id,
+        console::command_line(
+            "token-send",
+            &[&request.token_id, &request.address, &request.amount],
+        ),
+    )
+    .await
+}
+
+/// Builds the issuance of a new fungible token, returned unsubmitted.
+#[tauri::command]
+pub async fn issue_token(
+    state: State<'_, Mutex<AppState>>,
+    request: IssueTokenRequest,
+) -> Result<TransactionResult, AppError> {
+    let command = request.issuance.command_line()?;
+    console_transaction(&state, request.wallet_id, request.account_id, command).await
+}
+
+/// Builds a mint, unmint, supply lock, freeze, unfreeze, authority or metadata change of
+/// a token, returned unsubmitted.
+#[tauri::command]
+pub async fn manage_token(
+    state: State<'_, Mutex<AppState>>,
+    request: ManageTokenRequest,
+) -> Result<TransactionResult, AppError> {
+    let info = token_info(
+        &state,
+        request.wallet_id,
+        request.account_id,
+        &request.token_id,
+    )
+    .await?;
+    let command = request.action.command_line(&info)?;
+    let transaction =
+        console_transaction(&state, request.wallet_id, request.account_id, command).await?;
+
+    // The cached info is outdated once the transaction is submitted
+    let state = state.lock().await;
+    state.event_state.lock().await.token_infos.remove(&request.token_id);
+
+    Ok(transaction)
+}
+
+/// Builds the issuance of a new NFT, returned unsubmitted.
+#[tauri::command]
+pub async fn issue_nft(
+    state: State<'_, Mutex<AppState>>,
+    request: IssueNftRequest,
+) -> Result<TransactionResult, AppError> {
+    let command = request.issuance.command_line()?;
+    console_transaction(&state, request.wallet_id, request.account_id, command).await
+}
+
+/// Lists the NFTs held by an account, found among its unspent outputs.
+#[tauri::command]
+pub async fn list_nfts(
+    state: State<'_, Mutex<AppState>>,
+    wallet_id: WalletId,
+    account_id: AccountId,
+) -> Result<Vec<NftInfo>, AppError> {
+    let utxos = account_utxos(&state, wallet_id, account_id).await?;
+    let token_ids = utxos
+        .iter()
+        .filter_map(|utxo| utxo::token_id(utxo.output()))
+        .collect::<BTreeSet<_>>();
+
+    let mut nfts = Vec::new();
+    for token_id in token_ids {
+        let info = lookup_token(&state, wallet_id, account_id, token_id).await?;
+        nfts.extend(NftInfo::from_json(token_id, &info));
+    }
+    Ok(nfts)
+}
+
+/// Builds the transfer of an NFT, returned unsubmitted.
+#[tauri::command]
+pub async fn transfer_nft(
+    state: State<'_, Mutex<AppState>>,
+    request: TransferNftRequest,
+) -> Result<TransactionResult, AppError> {
+    Address::<Destination>::from_string(state.lock().await.chain_config()?, &request.address)
+        .map_err(|e| AppError::new(ErrorCode::InvalidAddress, e.to_string()))?;
+
+    // An NFT is a token with a supply of a single atom and no decimals
+    console_transaction(
+        &state,
+        request.wallet_id,
+        request.account_id,
+        console::command_line("token-send", &[&request.token_id, &request.address, "1"]),
+    )
+    .await
+}
+
+/// Signs a message with the key of one of the account's addresses, proving its ownership.
+/// Returns the hex encoded signature.
+#[tauri::command]
+pub async fn sign_message(
+    state: State<'_, Mutex<AppState>>,
+    wallet_id: WalletId,
+    account_id: AccountId,
+    message: String,
+    address: String,
+) -> Result<String, AppError> {
+    let output = console_command(
+        &state,
+        wallet_id,
+        account_id,
+        console::command_line("account-sign-challenge-plain", &[&message, &address]),
+    )
+    .await?;
+
+    let signature = signing::printed_signature(&output)?;
+
+    // A signature by another key means the output was not understood
+    let state = state.lock().await;
+    let verification =
+        signing::verify_message(state.chain_config()?, &message, &signature, &address);
+    if !verification.is_valid() {
+        return Err(AppError::new(
+            ErrorCode::Wallet,
+            format!("The signature in the output does not verify: {output}"),
+        ));
+    }
+    Ok(signature)
+}
+
+#[tauri::command]
+pub async fn verify_message(
+    state: State<'_, Mutex<AppState>>,
+    message: String,
+    signature: String,
+    address: String,
+) -> Result<MessageVerification, AppError> {
+    let state = state.lock().await;
+    Ok(signing::verify_message(
+        state.chain_config()?,
+        &message,
+        &signature,
+        &address,
+    ))
+}
+
+/// Saves a transaction built on the watch-only side to be carried to the cold wallet.
+#[tauri::command]
+pub async fn export_transaction_file(
+    state: State<'_, Mutex<AppState>>,
+    wallet_id: WalletId,
+    account_id: AccountId,
+    file_path: String,
+    transaction: String,
+) -> Result<TransactionFileResult, AppError> {
+    let network = state.lock().await.network()?;
+    let file = TransactionFile::new(network, transaction)?;
+    file.write(Path::new(&file_path))?;
+
+    inspect_transaction_file(&state, wallet_id, account_id, file.transaction()).await
+}
+
+/// Loads a transaction file, on the cold side to sign it or back on the watch-only side
+/// to submit it once signed.
+#[tauri::command]
+pub async fn import_transaction_file(
+    state: State<'_, Mutex<AppState>>,
+    wallet_id: WalletId,
+    account_id: AccountId,
+    file_path: String,
+) -> Result<TransactionFileResult, AppError> {
+    let network = state.lock().await.network()?;
+    let file = TransactionFile::read(Path::new(&file_path), network)?;
+
+    inspect_transaction_file(&state, wallet_id, account_id, file.transaction()).await
+}
+
+/// Adds the signatures the account can make to an imported transaction.
+#[tauri::command]
+pub async fn sign_transaction(
+    state: State<'_, Mutex<AppState>>,
+    wallet_id: WalletId,
+    account_id: AccountId,
+    transaction: String,
+) -> Result<TransactionFileResult, AppError> {
+    let output = console_command(
+        &state,
+        wallet_id,
+        account_id,
+        console::command_line("account-sign-raw-transaction", &[&transaction]),
+    )
+    .await?;
+    // Inputs the account has no keys for are left unsigned
+    let transaction = match console::printed_data::<SignedTransaction>(&output) {
+        Ok((transaction, _)) => transaction,
+        Err(_) => console::printed_data::<PartiallySignedTransaction>(&output)?.0,
+    };
+
+    inspect_transaction_file(&state, wallet_id, account_id, &transaction).await
+}
+
+/// Decodes a hex encoded transaction received from elsewhere, telling which of its
+/// inputs the open wallets can spend.
+#[tauri::command]
+pub async fn decode_transaction(
+    state: State<'_, Mutex<AppState>>,
+    transaction: String,
+) -> Result<DecodedTransaction, AppError> {
+    let accounts = {
+        let state = state.lock().await;
+        let event_state = state.event_state.lock().await;
+        event_state.event_cache.accounts()
+    };
+
+    let mut owned_utxos = Vec::new();
+    for (wallet_id, account_id) in accounts {
+        let cached = {
+            let state = state.lock().await;
+            let event_state = state.event_state.lock().await;
+            event_state.utxos.get(wallet_id, account_id).cloned()
+        };
+        let utxos = match cached {
+            Some(utxos) => utxos,
+            // Ownership is informative only, an account that cannot list its outputs
+            // just has no inputs in the transaction
+            None => match account_utxos(&state, wallet_id, account_id).await {
+                Ok(utxos) => {
+                    let state = state.lock().await;
+                    let mut event_state = state.event_state.lock().await;
+                    event_state.utxos.insert(wallet_id, account_id, utxos.clone());
+                    utxos
+                }
+                Err(_) => continue,
+            },
+        };
+        owned_utxos.push(((wallet_id, account_id), utxos));
+    }
+
+    let state = state.lock().await;
+    DecodedTransaction::decode(state.chain_config()?, &transaction, &owned_utxos)
+}
+
+/// Splits a hex encoded transaction into QR frames for an air-gapped wallet to scan.
+#[tauri::command]
+pub fn encode_qr_frames(
+    transaction: String,
+    chunk_len: Option<usize>,
+) -> Result<Vec<String>, AppError> {
+    qr_frames::encode(&transaction, chunk_len)
+}
+
+/// Reassembles a transaction from the QR frames scanned so far. Once complete, the
+/// transaction can be inspected and signed like an imported transaction file.
+#[tauri::command]
+pub fn decode_qr_frames(frames: Vec<String>) -> QrDecodeResult {
+    qr_frames::decode(&frames)
+}
+
+/// Sends every spendable coin of an account, or of the chosen outputs, to one address,
+/// taking the fee out of the sent amount. The signed transaction is returned with its
+/// fee to be confirmed like the other sends.
+#[tauri::command]
+pub async fn sweep_account_wrapper(
+    state: tauri::State<'_, Mutex<AppState>>,
+    request: SweepRequest,
+) -> Result<PreparedTransaction, AppError> {
+    let SweepRequest {
+        wallet_id,
+        account_id,
+        address,
+        inputs,
+        fee_rate,
+    } = request;
+
+    let utxos = account_utxos(&state, wallet_id, account_id).await?;
+    let utxos = if inputs.is_empty() {
+        utxos
+    } else {
+        utxo::chosen(&utxos, &inputs)?
+    };
+    if utxos.iter().all(|utxo| utxo.amount().is_none()) {
+        return Err(AppError::new(
+            ErrorCode::InsufficientFunds,
+            "No spendable coins",
+        ));
+    }
+
+    compose_transaction(
+        &state,
+        wallet_id,
+        account_id,
+        Payment::Sweep(address),
+        &utxos,
+        true,
+        &fee_rate,
+    )
+    .await
+}
+
+/// Pays several recipients in a single transaction built through the wallet console,
+/// spending the chosen outputs if any. Every output is validated before anything is
+/// built, and the signed transaction is returned with its fee to be confirmed like the
+/// other sends. Only coins can be paid this way, an output with a token is rejected.
+#[tauri::command]
+pub async fn batch_send_wrapper(
+    state: tauri::State<'_, Mutex<AppState>>,
+    request: BatchSendRequest,
+) -> Result<PreparedTransaction, AppError> {
+    let outputs = {
+        let state = state.lock().await;
+        payout::validate_outputs(state.chain_config()?, &request.outputs)?
+    };
+
+    let utxos = account_utxos(&state, request.wallet_id, request.account_id).await?;
+    let (utxos, spend_all) = if request.inputs.is_empty() {
+        (utxos, false)
+    } else {
+        (utxo::chosen(&utxos, &request.inputs)?, true)
+    };
+
+    compose_transaction(
+        &state,
+        request.wallet_id,
+        request.account_id,
+        Payment::Outputs(outputs),
+        &utxos,
+        spend_all,
+        &request.fee_rate,
+    )
+    .await
+}
+
+/// Reads a payout CSV file picked in the UI and previews the outputs it would pay.
+#[tauri::command]
+pub async fn preview_payout_csv(
+    state: State<'_, Mutex<AppState>>,
+    file_path: String,
+) -> Result<PayoutPreview, AppError> {
     let state = state.lock().await;
+    let chain_config = state.chain_config()?;
 
-    state
-        .backend_sender
-        .as_ref()
-        .expect("Backend sender must be initialized")
-        .send(BackendRequest::CloseWallet(wallet_id));
+    let data = std::fs::read(&file_path)
+        .map_err(|e| AppError::new(ErrorCode::Storage, format!("Cannot read {file_path}: {e}")))?;
 
-    Ok(())
+    Ok(payout::parse_csv(chain_config, &data))
 }
 
 #[tauri::command]
 pub async fn stake_amount_wrapper(
     state: tauri::State<'_, Mutex<AppState>>,
     request: StakeAmountRequest,
-) -> Result<(), String> {
+) -> Result<RequestId, AppError> {
+    utxo::check_no_inputs(&request.inputs, "a stake")?;
     let state = state.lock().await;
 
     let stake_request = StakeRequest {
@@ -435,20 +1747,23 @@ pub async fn stake_amount_wrapper(
         decommission_address: request.decommission_address,
     };
 
-    state
-        .backend_sender
-        .as_ref()
-        .expect("Backend sender must be initialized")
-        .send(BackendRequest::StakeAmount(stake_request));
+    let request_id = send_request(
+        &state,
+        "StakeAmount",
+        Some(stake_request.wallet_id),
+        Some(stake_request.account_id),
+        BackendRequest::StakeAmount(stake_request),
+    )
+    .await?;
 
-    Ok(())
+    Ok(request_id)
 }
 
 #[tauri::command]
 pub async fn decommission_pool_wrapper(
     state: tauri::State<'_, Mutex<AppState>>,
     request: DecommissionStakingPoolRequest,
-) -> Result<(), String> {
+) -> Result<RequestId, AppError> {
     let state = state.lock().await;
 
     let decommission_request = DecommissionPoolRequest {
@@ -458,20 +1773,23 @@ pub async fn decommission_pool_wrapper(
         output_address: request.output_address,
     };
 
-    state
-        .backend_sender
-        .as_ref()
-        .expect("Backend sender must be initialized")
-        .send(BackendRequest::DecommissionPool(decommission_request));
+    let request_id = send_request(
+        &state,
+        "DecommissionPool",
+        Some(decommission_request.wallet_id),
+        Some(decommission_request.account_id),
+        BackendRequest::DecommissionPool(decommission_request),
+    )
+    .await?;
 
-    Ok(())
+    Ok(request_id)
 }
 
 #[tauri::command]
 pub async fn create_delegation_wrapper(
     state: tauri::State<'_, Mutex<AppState>>,
     request: DelegationCreateRequest,
-) -> Result<(), String> {
+) -> Result<RequestId, AppError> {
     let state = state.lock().await;
 
     let delegation_request = CreateDelegationRequest {
@@ -481,20 +1799,24 @@ pub async fn create_delegation_wrapper(
         delegation_address: request.delegation_address,
     };
 
-    state
-        .backend_sender
-        .as_ref()
-        .expect("Backend sender must be initialized")
-        .send(BackendRequest::CreateDelegation(delegation_request));
+    let request_id = send_request(
+        &state,
+        "CreateDelegation",
+        Some(delegation_request.wallet_id),
+        Some(delegation_request.account_id),
+        BackendRequest::CreateDelegation(delegation_request),
+    )
+    .await?;
 
-    Ok(())
+    Ok(request_id)
 }
 
 #[tauri::command]
 pub async fn delegate_staking_wrapper(
     state: tauri::State<'_, Mutex<AppState>>,
     request: StakingDelegateRequest,
-) -> Result<(), String> {
+) -> Result<RequestId, AppError> {
+    utxo::check_no_inputs(&request.inputs, "a delegation")?;
     let state = state.lock().await;
 
     let delegation_request = DelegateStakingRequest {
@@ -504,20 +1826,23 @@ pub async fn delegate_staking_wrapper(
         delegation_amount: request.delegation_amount,
     };
 
-    state
-        .backend_sender
-        .as_ref()
-        .expect("Backend sender must be initialized")
-        .send(BackendRequest::DelegateStaking(delegation_request));
+    let request_id = send_request(
+        &state,
+        "DelegateStaking",
+        Some(delegation_request.wallet_id),
+        Some(delegation_request.account_id),
+        BackendRequest::DelegateStaking(delegation_request),
+    )
+    .await?;
 
-    Ok(())
+    Ok(request_id)
 }
 
 #[tauri::command]
 pub async fn send_delegation_to_address_wrapper(
     state: tauri::State<'_, Mutex<AppState>>,
     request: SendDelegateRequest,
-) -> Result<(), String> {
+) -> Result<RequestId, AppError> {
     let state = state.lock().await;
 
     let send_delegation_request = SendDelegateToAddressRequest {
@@ -528,85 +1853,647 @@ pub async fn send_delegation_to_address_wrapper(
         delegation_id: request.delegation_id,
     };
 
-    state.backend_sender.as_ref().expect("Backend sender must be initialized").send(
+    let request_id = send_request(
+        &state,
+        "SendDelegationToAddress",
+        Some(send_delegation_request.wallet_id),
+        Some(send_delegation_request.account_id),
         BackendRequest::SendDelegationToAddress(send_delegation_request),
-    );
+    )
+    .await?;
 
-    Ok(())
+    Ok(request_id)
 }
 
 #[tauri::command]
 pub async fn new_account_wrapper(
     state: tauri::State<'_, Mutex<AppState>>,
     request: NewAccountRequest,
-) -> Result<(), String> {
+) -> Result<RequestId, AppError> {
     let state = state.lock().await;
 
-    state.backend_sender.as_ref().expect("Backend sender must be initialized").send(
+    let request_id = send_request(
+        &state,
+        "NewAccount",
+        Some(request.wallet_id),
+        None,
         BackendRequest::NewAccount {
             wallet_id: request.wallet_id,
             name: request.name,
         },
-    );
+    )
+    .await?;
 
-    Ok(())
+    Ok(request_id)
 }
 
 #[tauri::command]
 pub async fn toggle_staking_wrapper(
     state: tauri::State<'_, Mutex<AppState>>,
     request: ToggleStakingRequest,
-) -> Result<(), String> {
+) -> Result<RequestId, AppError> {
     let state = state.lock().await;
 
-    state.backend_sender.as_ref().expect("Backend sender must be initialized").send(
+    let request_id = send_request(
+        &state,
+        "ToggleStaking",
+        Some(request.wallet_id),
+        Some(request.account_id),
         BackendRequest::ToggleStaking(request.wallet_id, request.account_id, request.enabled),
-    );
-    Ok(())
+    )
+    .await?;
+
+    Ok(request_id)
 }
 
 #[tauri::command]
 pub async fn handle_console_command_wrapper(
     state: tauri::State<'_, Mutex<AppState>>,
     request: ConsoleRequest,
-) -> Result<(), String> {
+) -> Result<RequestId, AppError> {
+    let console_broadcast = state.lock().await.console_broadcast.clone();
+    // Waits for a transaction being built through the console to be done with it
+    let mut console_broadcast = console_broadcast.lock().await;
+    console_broadcast.observe(request.wallet_id, &request.command);
+
     let state = state.lock().await;
-    state.backend_sender.as_ref().expect("Backend sender must be initialized").send(
+
+    let request_id = send_request(
+        &state,
+        "ConsoleResponse",
+        Some(request.wallet_id),
+        Some(request.account_id),
         BackendRequest::ConsoleCommand {
             wallet_id: request.wallet_id,
             account_id: request.account_id,
             command: request.command,
         },
-    );
-    Ok(())
+    )
+    .await?;
+
+    Ok(request_id)
 }
 
 #[tauri::command]
 pub async fn submit_transaction_wrapper(
     state: tauri::State<'_, Mutex<AppState>>,
     request: SubmitTransactionRequest,
-) -> Result<(), String> {
+) -> Result<RequestId, AppError> {
     let state = state.lock().await;
 
-    state.backend_sender.as_ref().expect("Backend sender must be initialized").send(
+    let request_id = send_request(
+        &state,
+        "Broadcast",
+        Some(request.wallet_id),
+        None,
         BackendRequest::SubmitTx {
             wallet_id: request.wallet_id,
             tx: request.tx.tx,
         },
-    );
+    )
+    .await?;
+
+    Ok(request_id)
+}
+
+/// Broadcasts a signed transaction produced outside of the app, such as by a script,
+/// another wallet or a cold signer.
+///
+/// The transaction is relayed through the wallet `wallet_id`, whatever its inputs, and
+/// the node checks it against the mempool rules; a rejection is part of the result.
+#[tauri::command]
+pub async fn broadcast_transaction(
+    state: State<'_, Mutex<AppState>>,
+    wallet_id: WalletId,
+    transaction: String,
+) -> Result<BroadcastResult, AppError> {
+    let transaction = decode_signed_transaction(&transaction)?;
+    let unsigned = transaction
+        .signatures()
+        .iter()
+        .any(|witness| matches!(witness, InputWitness::NoSignature(_)));
+    if unsigned {
+        return Err(AppError::invalid_input(
+            "Not every input of the transaction is signed",
+        ));
+    }
+    let tx_id = transaction.transaction().get_id();
 
-    Ok(())
+    let result = send_request_and_wait(
+        &state,
+        "Broadcast",
+        Some(wallet_id),
+        None,
+        BackendRequest::SubmitTx {
+            wallet_id,
+            tx: SignedTransactionWrapper::new(transaction),
+        },
+    )
+    .await;
+
+    match result {
+        Ok(_) => Ok(BroadcastResult::new(tx_id, None)),
+        // Any error but the node refusing the transaction means it was not submitted
+        Err(error) => match Rejection::from_error(&error) {
+            Some(rejection) => Ok(BroadcastResult::new(tx_id, Some(rejection))),
+            None => Err(error),
+        },
+    }
 }
 
+/// Searches the whole transaction history of an account, with filters, sorting and
+/// pagination.
 #[tauri::command]
-pub async fn shutdown_wrapper(state: tauri::State<'_, Mutex<AppState>>) -> Result<(), String> {
+pub async fn query_transactions(
+    state: State<'_, Mutex<AppState>>,
+    wallet_id: WalletId,
+    account_id: AccountId,
+    query: HistoryQuery,
+) -> Result<HistoryPage, AppError> {
+    let txs = transaction_history(&state, wallet_id, account_id).await?;
+    let transactions = match query.address {
+        Some(_) => history_transactions(&state, wallet_id, account_id, &txs).await?,
+        None => history::Transactions::new(),
+    };
+
+    let state = state.lock().await;
+    query.apply(state.chain_config()?, txs, &transactions)
+}
+
+/// Looks up the transactions of an account history, fetching the ones not known yet
+/// with the `transaction-get-raw` console command.
+async fn history_transactions(
+    state: &Mutex<AppState>,
+    wallet_id: WalletId,
+    account_id: AccountId,
+    txs: &[serde_json::Value],
+) -> Result<history::Transactions, AppError> {
+    let txids = txs
+        .iter()
+        .filter_map(|tx| tx.get("txid")?.as_str())
+        .map(str::to_owned)
+        .collect::<Vec<_>>();
+    let missing = {
+        let state = state.lock().await;
+        let event_state = state.event_state.lock().await;
+        txids
+            .iter()
+            .filter(|txid| !event_state.transactions.contains_key(*txid))
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+
+    for txid in missing {
+        let output = console_command(
+            state,
+            wallet_id,
+            account_id,
+            console::command_line("transaction-get-raw", &[&txid]),
+        )
+        .await?;
+        let (_, transaction) = console::printed_data::<Transaction>(&output)?;
+
+        let state = state.lock().await;
+        state.event_state.lock().await.transactions.insert(txid, transaction);
+    }
+
     let state = state.lock().await;
+    let event_state = state.event_state.lock().await;
+    Ok(txids
+        .into_iter()
+        .filter_map(|txid| {
+            let transaction = event_state.transactions.get(&txid)?.clone();
+            Some((txid, transaction))
+        })
+        .collect())
+}
+
+/// Returns every transaction of an account, fetched one backend page after the other
+/// unless known since the last refresh of its transaction list.
+async fn transaction_history(
+    state: &Mutex<AppState>,
+    wallet_id: WalletId,
+    account_id: AccountId,
+) -> Result<Vec<serde_json::Value>, AppError> {
+    let (cached, shown_skip) = {
+        let state = state.lock().await;
+        let event_state = state.event_state.lock().await;
+        (
+            event_state.histories.get(wallet_id, account_id).cloned(),
+            event_state
+                .event_cache
+                .transaction_list_skip(wallet_id, account_id)
+                .unwrap_or(0),
+        )
+    };
+    if let Some(txs) = cached {
+        return Ok(txs);
+    }
+
+    let txs = fetch_transaction_history(state, wallet_id, account_id).await;
+
+    // The backend keeps refreshing the page it was last asked for, which has to be the
+    // one shown in the UI again
+    let state = state.lock().await;
+    state.backend_sender()?.send(BackendRequest::TransactionList {
+        wallet_id,
+        account_id,
+        skip: shown_skip,
+    });
+
+    let txs = txs?;
+    let mut event_state = state.event_state.lock().await;
+    event_state.histories.insert(wallet_id, account_id, txs.clone());
+    Ok(txs)
+}
+
+async fn fetch_transaction_history(
+    state: &Mutex<AppState>,
+    wallet_id: WalletId,
+    account_id: AccountId,
+) -> Result<Vec<serde_json::Value>, AppError> {
+    let mut txs = Vec::new();
+    loop {
+        let page = send_request_and_wait(
+            state,
+            "TransactionList",
+            Some(wallet_id),
+            Some(account_id),
+            BackendRequest::TransactionList {
+                wallet_id,
+                account_id,
+                skip: txs.len(),
+            },
+        )
+        .await?;
+
+        let list = &page["transaction_list"];
+        let (Some(skip), Some(total), Some(page_txs)) = (
+            list["skip"].as_u64(),
+            list["total"].as_u64(),
+            list["txs"].as_array(),
+        ) else {
+            return Err(AppError::new(
+                ErrorCode::Backend,
+                format!("Unexpected transaction list: {list}"),
+            ));
+        };
+
+        if skip as usize != txs.len() {
+            return Err(AppError::new(
+                ErrorCode::Backend,
+                format!("Transaction list page at {skip} instead of {}", txs.len()),
+            ));
+        }
+        txs.extend(page_txs.iter().cloned());
+        if page_txs.is_empty() || txs.len() as u64 >= total {
+            return Ok(txs);
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn shutdown_wrapper<R: Runtime>(
+    app_handle: AppHandle<R>,
+    state: tauri::State<'_, Mutex<AppState>>,
+) -> Result<(), AppError> {
+    stop_node(&app_handle, &state).await
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{
+        chain::{DelegationId, OutPointSourceId, SignedTransaction, Transaction, TxInput},
+        primitives::{Id, H256},
+    };
+    use serde_json::json;
+    use tauri::{test::MockRuntime, App, Manager};
+
+    use super::*;
+
+    const ADDRESS: &str = "tmt1qyltm78pn55rvjhjedjlyv4ue0wanhkkfgszn7le";
+    const TOKEN_ID: &str = "tmltk1jzgup986mh3x9n5024svm4wtuf2qp5vedlgy5632wah0pjffwhpqgsvmuq";
+
+    fn uninitialized_app() -> App<MockRuntime> {
+        let app = tauri::test::mock_app();
+        // The commands fail before touching any file, the directory does not have to exist
+        let config_dir = std::env::temp_dir().join("node-gui-x-uninitialized");
+        app.manage(Mutex::new(AppState::new(&config_dir)));
+        app
+    }
 
-    state
-        .backend_sender
-        .as_ref()
-        .expect("Backend sender must be initialized")
-        .send(BackendRequest::Shutdown);
+    fn from_json<T: serde::de::DeserializeOwned>(value: serde_json::Value) -> T {
+        serde_json::from_value(value).expect("valid request")
+    }
+
+    fn send_request() -> SendAmountRequest {
+        from_json(json!({
+            "wallet_id": 0,
+            "account_id": 0,
+            "amount": "1",
+            "address": ADDRESS,
+        }))
+    }
+
+    fn transaction_hex() -> String {
+        let transaction = Transaction::new(0, Vec::new(), Vec::new()).expect("valid transaction");
+        let signed = SignedTransaction::new(transaction, Vec::new()).expect("valid transaction");
+        hex::encode(parity_scale_codec::Encode::encode(&signed))
+    }
+
+    macro_rules! assert_not_initialized {
+        ($call:expr) => {
+            match $call.await {
+                Err(e) => assert_eq!(e.code(), ErrorCode::NodeNotInitialized, "{e}"),
+                Ok(_) => panic!("{} succeeded without a node", stringify!($call)),
+            }
+        };
+    }
+
+    #[test]
+    fn commands_fail_without_a_node() {
+        let app = uninitialized_app();
+        let state = || app.state::<Mutex<AppState>>();
+        let wallet_id: WalletId = from_json(json!(0));
+        let account_id: AccountId = from_json(json!(0));
+
+        tauri::async_runtime::block_on(async {
+            assert_not_initialized!(get_stake_pool_maturity_distance(
+                state(),
+                BlockHeight::new(0)
+            ));
+            assert_not_initialized!(add_create_wallet_wrapper(
+                state(),
+                from_json(json!({
+                    "mnemonic": mnemonic::generate(),
+                    "file_path": "wallet.dat",
+                    "import": true,
+                    "wallet_type": "Hot",
+                }))
+            ));
+            assert_not_initialized!(add_open_wallet_wrapper(
+                state(),
+                from_json(json!({ "file_path": "wallet.dat", "wallet_type": "Hot" }))
+            ));
+            assert_not_initialized!(list_utxos(state(), wallet_id, account_id));
+            assert_not_initialized!(estimate_fee(state(), send_request()));
+            assert_not_initialized!(send_amount_wrapper(
+                app.handle().clone(),
+                state(),
+                send_request()
+            ));
+            assert_not_initialized!(sweep_account_wrapper(
+                state(),
+                from_json(json!({ "wallet_id": 0, "account_id": 0, "address": ADDRESS }))
+            ));
+            assert_not_initialized!(batch_send_wrapper(
+                state(),
+                from_json(json!({
+                    "wallet_id": 0,
+                    "account_id": 0,
+                    "outputs": [{ "address": ADDRESS, "amount": "1" }],
+                }))
+            ));
+            assert_not_initialized!(preview_payout_csv(state(), "payout.csv".to_owned()));
+            assert_not_initialized!(get_token_info(
+                state(),
+                wallet_id,
+                account_id,
+                TOKEN_ID.to_owned()
+            ));
+            assert_not_initialized!(send_tokens(
+                state(),
+                from_json(json!({
+                    "wallet_id": 0,
+                    "account_id": 0,
+                    "token_id": TOKEN_ID,
+                    "address": ADDRESS,
+                    "amount": "1",
+                }))
+            ));
+            assert_not_initialized!(issue_token(
+                state(),
+                from_json(json!({
+                    "wallet_id": 0,
+                    "account_id": 0,
+                    "issuance": {
+                        "ticker": "TKN",
+                        "decimals": 2,
+                        "metadata_uri": "",
+                        "destination_address": ADDRESS,
+                        "supply": "Unlimited",
+                        "freezable": false,
+                    },
+                }))
+            ));
+            assert_not_initialized!(manage_token(
+                state(),
+                from_json(json!({
+                    "wallet_id": 0,
+                    "account_id": 0,
+                    "token_id": TOKEN_ID,
+                    "action": "LockSupply",
+                }))
+            ));
+            assert_not_initialized!(issue_nft(
+                state(),
+                from_json(json!({
+                    "wallet_id": 0,
+                    "account_id": 0,
+                    "issuance": {
+                        "name": "Certificate",
+                        "ticker": "CRT",
+                        "description": "Test",
+                        "media_hash": "00",
+                        "destination_address": ADDRESS,
+                    },
+                }))
+            ));
+            assert_not_initialized!(list_nfts(state(), wallet_id, account_id));
+            assert_not_initialized!(transfer_nft(
+                state(),
+                from_json(json!({
+                    "wallet_id": 0,
+                    "account_id": 0,
+                    "token_id": TOKEN_ID,
+                    "address": ADDRESS,
+                }))
+            ));
+            assert_not_initialized!(sign_message(
+                state(),
+                wallet_id,
+                account_id,
+                "message".to_owned(),
+                ADDRESS.to_owned()
+            ));
+            assert_not_initialized!(verify_message(
+                state(),
+                "message".to_owned(),
+                "00".to_owned(),
+                ADDRESS.to_owned()
+            ));
+            assert_not_initialized!(export_transaction_file(
+                state(),
+                wallet_id,
+                account_id,
+                "transaction.json".to_owned(),
+                transaction_hex()
+            ));
+            assert_not_initialized!(import_transaction_file(
+                state(),
+                wallet_id,
+                account_id,
+                "transaction.json".to_owned()
+            ));
+            assert_not_initialized!(sign_transaction(
+                state(),
+                wallet_id,
+                account_id,
+                transaction_hex()
+            ));
+            assert_not_initialized!(decode_transaction(state(), transaction_hex()));
+            assert_not_initialized!(broadcast_transaction(state(), wallet_id, transaction_hex()));
+            assert_not_initialized!(query_transactions(
+                state(),
+                wallet_id,
+                account_id,
+                HistoryQuery::default()
+            ));
+            assert_not_initialized!(new_address_wrapper(
+                state(),
+                from_json(json!({ "wallet_id": 0, "account_id": 0 }))
+            ));
+            assert_not_initialized!(update_encryption_wrapper(
+                state(),
+                from_json(json!({ "wallet_id": 0, "action": "lock", "password": null }))
+            ));
+            assert_not_initialized!(close_wallet_wrapper(state(), wallet_id));
+            assert_not_initialized!(stake_amount_wrapper(
+                state(),
+                from_json(json!({
+                    "wallet_id": 0,
+                    "account_id": 0,
+                    "pledge_amount": "40000",
+                    "mpt": "0.1",
+                    "cost_per_block": "1",
+                    "decommission_address": ADDRESS,
+                }))
+            ));
+            assert_not_initialized!(decommission_pool_wrapper(
+                state(),
+                from_json(json!({
+                    "wallet_id": 0,
+                    "account_id": 0,
+                    "pool_id": "",
+                    "output_address": ADDRESS,
+                }))
+            ));
+            assert_not_initialized!(create_delegation_wrapper(
+                state(),
+                from_json(json!({
+                    "wallet_id": 0,
+                    "account_id": 0,
+                    "pool_id": "",
+                    "delegation_address": ADDRESS,
+                }))
+            ));
+            assert_not_initialized!(delegate_staking_wrapper(
+                state(),
+                StakingDelegateRequest {
+                    wallet_id,
+                    account_id,
+                    delegation_id: DelegationId::new(H256::zero()),
+                    delegation_amount: "1".to_owned(),
+                    inputs: Vec::new(),
+                }
+            ));
+            assert_not_initialized!(send_delegation_to_address_wrapper(
+                state(),
+                from_json(json!({
+                    "wallet_id": 0,
+                    "account_id": 0,
+                    "address": ADDRESS,
+                    "amount": "1",
+                    "delegation_id": "",
+                }))
+            ));
+            assert_not_initialized!(new_account_wrapper(
+                state(),
+                from_json(json!({ "wallet_id": 0, "name": "Savings" }))
+            ));
+            assert_not_initialized!(toggle_staking_wrapper(
+                state(),
+                from_json(json!({ "wallet_id": 0, "account_id": 0, "enabled": true }))
+            ));
+            assert_not_initialized!(handle_console_command_wrapper(
+                state(),
+                from_json(json!({ "wallet_id": 0, "account_id": 0, "command": "help" }))
+            ));
+            let transaction = Transaction::new(0, Vec::new(), Vec::new()).expect("valid");
+            assert_not_initialized!(submit_transaction_wrapper(
+                state(),
+                SubmitTransactionRequest {
+                    wallet_id,
+                    tx: TransactionInfo {
+                        wallet_id,
+                        tx: SignedTransactionWrapper::new(
+                            SignedTransaction::new(transaction, Vec::new()).expect("valid")
+                        ),
+                    },
+                }
+            ));
+            assert_not_initialized!(list_contacts(state()));
+            assert_not_initialized!(find_contacts(state(), "tmt".to_owned()));
+            assert_not_initialized!(add_contact(state(), "Alice".to_owned(), ADDRESS.to_owned()));
+            assert_not_initialized!(update_contact(
+                state(),
+                ADDRESS.to_owned(),
+                from_json(json!({ "label": "Alice", "address": ADDRESS }))
+            ));
+            assert_not_initialized!(delete_contact(state(), ADDRESS.to_owned()));
+        });
+    }
 
-    Ok(())
+    #[test]
+    fn broadcasts_are_checked_before_reaching_the_node() {
+        let app = uninitialized_app();
+        let state = || app.state::<Mutex<AppState>>();
+        let wallet_id: WalletId = from_json(json!(0));
+
+        let input = TxInput::from_utxo(OutPointSourceId::Transaction(Id::new(H256::zero())), 0);
+        let transaction = Transaction::new(0, vec![input], Vec::new()).expect("valid");
+        let unsigned = SignedTransaction::new(transaction, vec![InputWitness::NoSignature(None)])
+            .expect("valid");
+        let unsigned = hex::encode(parity_scale_codec::Encode::encode(&unsigned));
+
+        tauri::async_runtime::block_on(async {
+            for transaction in [unsigned, "not hex".to_owned(), "00".to_owned()] {
+                let error = broadcast_transaction(state(), wallet_id, transaction)
+                    .await
+                    .expect_err("not broadcast");
+                assert_eq!(error.code(), ErrorCode::InvalidInput, "{error}");
+            }
+        });
+    }
+
+    #[test]
+    fn node_independent_commands_work_without_a_node() {
+        let app = uninitialized_app();
+        let state = || app.state::<Mutex<AppState>>();
+
+        tauri::async_runtime::block_on(async {
+            assert_eq!(
+                get_node_state(state()).await.expect("node state"),
+                NodeState::Uninitialized
+            );
+            assert_eq!(get_network(state()).await.expect("network"), None);
+            assert!(list_open_wallets(state()).await.expect("open wallets").is_empty());
+            // Shutting down a node that never started is a no-op
+            shutdown_wrapper(app.handle().clone(), state()).await.expect("shutdown");
+            assert_eq!(
+                get_node_state(state()).await.expect("node state"),
+                NodeState::Uninitialized
+            );
+        });
+    }
 }
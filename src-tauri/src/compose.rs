@@ -0,0 +1,352 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/node-gui-x/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Reverse;
+
+use common::{chain::Transaction, primitives::Amount};
+use serde_json::Value;
+
+use crate::{
+    error::{AppError, ErrorCode},
+    fee::{FeeEstimate, FeeRate},
+    utxo::UtxoEntry,
+};
+
+/// Rounds of composing and signing allowed for the fee to cover the signed transaction.
+///
+/// Each round only raises the fee, so a couple of rounds are enough in practice.
+pub const MAX_FEE_ROUNDS: usize = 5;
+
+/// Fee asked by the mempool, as the fee of a transaction of known size.
+#[derive(Debug, Clone, Copy)]
+pub struct FeeQuote {
+    fee: Amount,
+    size: usize,
+}
+
+impl FeeQuote {
+    pub fn new(fee: Amount, size: usize) -> Self {
+        FeeQuote { fee, size }
+    }
+
+    /// The quote at `rate`, `self` being the mempool rate.
+    pub fn at_rate(self, rate: &FeeRate, coin_decimals: u8) -> Result<Self, AppError> {
+        let (numerator, denominator) = match rate {
+            FeeRate::Low => (1, 2),
+            FeeRate::Normal => (1, 1),
+            FeeRate::High => (2, 1),
+            FeeRate::PerKb(coins) => {
+                let fee = Amount::from_fixedpoint_str(coins, coin_decimals).ok_or_else(|| {
+                    AppError::new(
+                        ErrorCode::InvalidAmount,
+                        format!("Invalid fee rate: {coins}"),
+                    )
+                })?;
+                return Ok(FeeQuote::new(fee, 1000));
+            }
+        };
+        let atoms = self
+            .fee
+            .into_atoms()
+            .checked_mul(numerator)
+            .ok_or_else(overflow)?
+            .div_ceil(denominator);
+        Ok(FeeQuote::new(Amount::from_atoms(atoms), self.size))
+    }
+
+    /// Fee of a transaction of `size` bytes at the same rate, rounded up.
+    pub fn fee_for(&self, size: usize) -> Result<Amount, AppError> {
+        let atoms = self
+            .fee
+            .into_atoms()
+            .checked_mul(size as u128)
+            .ok_or_else(overflow)?
+            .div_ceil(self.size.max(1) as u128);
+        Ok(Amount::from_atoms(atoms))
+    }
+}
+
+/// What a composed transaction pays.
+#[derive(Debug, Clone)]
+pub enum Payment {
+    /// Amounts to addresses, what the inputs hold beyond them and the fee goes back to
+    /// the account
+    Outputs(Vec<(String, Amount)>),
+    /// Everything the inputs hold minus the fee, to a single address
+    Sweep(String),
+}
+
+impl Payment {
+    /// Address the mempool fee is priced with.
+    pub fn first_address(&self) -> Option<&str> {
+        match self {
+            Payment::Outputs(outputs) => outputs.first().map(|(address, _)| address.as_str()),
+            Payment::Sweep(address) => Some(address),
+        }
+    }
+}
+
+/// Inputs and outputs of a transaction for the `transaction-compose` console command,
+/// which spends exactly what it is given and adds neither change nor fee.
+#[derive(Debug, Clone)]
+pub struct Draft {
+    inputs: Vec<UtxoEntry>,
+    outputs: Vec<(String, Amount)>,
+    payment: Amount,
+    change: Amount,
+    fee: Amount,
+}
+
+impl Draft {
+    /// Picks the inputs and outputs making `payment` and paying `fee`.
+    ///
+    /// With `spend_all` every coin output of `utxos` is spent, otherwise the largest ones
+    /// are taken until they cover the payment and the fee. Change goes back to the
+    /// address of the first input.
+    pub fn new(
+        payment: &Payment,
+        utxos: &[UtxoEntry],
+        spend_all: bool,
+        fee: Amount,
+    ) -> Result<Self, AppError> {
+        let mut coins = utxos.iter().filter(|utxo| utxo.amount().is_some()).collect::<Vec<_>>();
+        coins.sort_by_key(|utxo| Reverse(utxo.amount()));
+
+        let payment_total = match payment {
+            Payment::Outputs(outputs) => sum(outputs.iter().map(|(_, amount)| *amount))?,
+            Payment::Sweep(_) => Amount::ZERO,
+        };
+        let needed = (payment_total + fee).ok_or_else(overflow)?;
+
+        let mut inputs = Vec::new();
+        let mut input_total = Amount::ZERO;
+        for utxo in coins {
+            if !spend_all && !inputs.is_empty() && input_total >= needed {
+                break;
+            }
+            input_total =
+                (input_total + utxo.amount().unwrap_or(Amount::ZERO)).ok_or_else(overflow)?;
+            inputs.push(utxo.clone());
+        }
+        let rest = (input_total - needed).filter(|_| !inputs.is_empty()).ok_or_else(|| {
+            AppError::new(
+                ErrorCode::InsufficientFunds,
+                "Not enough coins to pay the outputs and the fee",
+            )
+        })?;
+
+        let (outputs, payment, change) = match payment {
+            Payment::Outputs(outputs) => {
+                let mut outputs = outputs.clone();
+                if rest > Amount::ZERO {
+                    let change_address = inputs[0].address().ok_or_else(|| {
+                        AppError::new(ErrorCode::Wallet, "Input without an address")
+                    })?;
+                    outputs.push((change_address.to_owned(), rest));
+                }
+                (outputs, payment_total, rest)
+            }
+            Payment::Sweep(address) => {
+                if rest == Amount::ZERO {
+                    return Err(AppError::new(
+                        ErrorCode::InsufficientFunds,
+                        "Not enough coins to pay the fee",
+                    ));
+                }
+                (vec![(address.clone(), rest)], rest, Amount::ZERO)
+            }
+        };
+
+        Ok(Draft {
+            inputs,
+            outputs,
+            payment,
+            change,
+            fee,
+        })
+    }
+
+    pub fn fee(&self) -> Amount {
+        self.fee
+    }
+
+    /// Whether `transaction` spends the inputs and has the outputs of the draft, as
+    /// far as their number tells.
+    pub fn matches(&self, transaction: &Transaction) -> bool {
+        transaction.inputs().len() == self.inputs.len()
+            && transaction.outputs().len() == self.outputs.len()
+    }
+
+    /// Arguments of the `transaction-compose` command building this transaction.
+    pub fn compose_args(&self, coin_decimals: u8) -> Result<Vec<String>, AppError> {
+        let mut args = self
+            .outputs
+            .iter()
+            .map(|(address, amount)| {
+                format!(
+                    "transfer({address},{})",
+                    amount.into_fixedpoint_str(coin_decimals)
+                )
+            })
+            .collect::<Vec<_>>();
+        for input in &self.inputs {
+            args.push("--utxos".to_owned());
+            args.push(outpoint_arg(input.outpoint()).ok_or_else(|| {
+                AppError::new(
+                    ErrorCode::Wallet,
+                    format!("Unexpected outpoint: {}", input.outpoint()),
+                )
+            })?);
+        }
+        Ok(args)
+    }
+
+    pub fn estimate(&self) -> Result<FeeEstimate, AppError> {
+        FeeEstimate::from_parts(
+            self.inputs.iter().map(|input| input.outpoint().clone()).collect(),
+            self.payment,
+            self.change,
+            self.fee,
+        )
+    }
+}
+
+/// Writes an outpoint the way the console parses it, `tx(<id>,<index>)` or
+/// `block(<id>,<index>)`.
+fn outpoint_arg(outpoint: &Value) -> Option<String> {
+    let index = outpoint.get("index")?.as_u64()?;
+    let id = outpoint.get("id")?;
+    if let Some(tx_id) = id.get("Transaction").and_then(Value::as_str) {
+        Some(format!("tx({tx_id},{index})"))
+    } else {
+        let block_id = id.get("BlockReward").and_then(Value::as_str)?;
+        Some(format!("block({block_id},{index})"))
+    }
+}
+
+fn sum(amounts: impl Iterator<Item = Amount>) -> Result<Amount, AppError> {
+    amounts.try_fold(Amount::ZERO, |sum, amount| sum + amount).ok_or_else(overflow)
+}
+
+fn overflow() -> AppError {
+    AppError::new(ErrorCode::InvalidAmount, "Amount overflow")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn utxo(index: u64, atoms: u128) -> UtxoEntry {
+        UtxoEntry::from_json(
+            &json!({
+                "outpoint": { "id": { "Transaction": format!("{index:064x}") }, "index": index },
+                "output": { "Transfer": [{ "Coin": { "atoms": atoms.to_string() } }, "own"] },
+            }),
+            false,
+        )
+        .unwrap()
+    }
+
+    fn pay(atoms: u128) -> Payment {
+        Payment::Outputs(vec![("them".to_owned(), Amount::from_atoms(atoms))])
+    }
+
+    #[test]
+    fn largest_inputs_are_taken_first() {
+        let utxos = [utxo(1, 10), utxo(2, 50), utxo(3, 30)];
+        let draft = Draft::new(&pay(60), &utxos, false, Amount::from_atoms(5)).unwrap();
+
+        assert_eq!(draft.inputs.len(), 2);
+        assert_eq!(draft.change, Amount::from_atoms(15));
+        assert_eq!(
+            draft.outputs,
+            [
+                ("them".to_owned(), Amount::from_atoms(60)),
+                ("own".to_owned(), Amount::from_atoms(15)),
+            ]
+        );
+    }
+
+    #[test]
+    fn exact_amount_has_no_change() {
+        let draft = Draft::new(&pay(45), &[utxo(1, 50)], false, Amount::from_atoms(5)).unwrap();
+
+        assert_eq!(draft.change, Amount::ZERO);
+        assert_eq!(draft.outputs.len(), 1);
+    }
+
+    #[test]
+    fn sweep_spends_everything_minus_the_fee() {
+        let utxos = [utxo(1, 10), utxo(2, 50)];
+        let payment = Payment::Sweep("them".to_owned());
+        let draft = Draft::new(&payment, &utxos, true, Amount::from_atoms(7)).unwrap();
+
+        assert_eq!(draft.inputs.len(), 2);
+        assert_eq!(draft.outputs, [("them".to_owned(), Amount::from_atoms(53))]);
+        assert_eq!(draft.change, Amount::ZERO);
+    }
+
+    #[test]
+    fn missing_coins_are_reported() {
+        let error = Draft::new(&pay(60), &[utxo(1, 50)], false, Amount::from_atoms(5)).unwrap_err();
+        assert_eq!(error.code(), ErrorCode::InsufficientFunds);
+
+        let payment = Payment::Sweep("them".to_owned());
+        let error = Draft::new(&payment, &[utxo(1, 5)], true, Amount::from_atoms(5)).unwrap_err();
+        assert_eq!(error.code(), ErrorCode::InsufficientFunds);
+    }
+
+    #[test]
+    fn compose_args_list_outputs_then_inputs() {
+        let draft = Draft::new(&pay(123), &[utxo(1, 200)], false, Amount::from_atoms(77)).unwrap();
+
+        assert_eq!(
+            draft.compose_args(2).unwrap(),
+            [
+                "transfer(them,1.23)".to_owned(),
+                "--utxos".to_owned(),
+                format!("tx({:064x},1)", 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn fee_scales_with_size() {
+        let quote = FeeQuote::new(Amount::from_atoms(100), 200);
+
+        assert_eq!(quote.fee_for(400).unwrap(), Amount::from_atoms(200));
+        assert_eq!(quote.fee_for(201).unwrap(), Amount::from_atoms(101));
+    }
+
+    #[test]
+    fn fee_rates_scale_the_mempool_rate() {
+        let quote = FeeQuote::new(Amount::from_atoms(101), 200);
+        let fee = |rate: FeeRate| quote.at_rate(&rate, 2).unwrap().fee_for(200).unwrap();
+
+        assert_eq!(fee(FeeRate::Low), Amount::from_atoms(51));
+        assert_eq!(fee(FeeRate::Normal), Amount::from_atoms(101));
+        assert_eq!(fee(FeeRate::High), Amount::from_atoms(202));
+        // 0.50 coins per 1000 bytes
+        assert_eq!(
+            fee(FeeRate::PerKb("0.5".to_owned())),
+            Amount::from_atoms(10)
+        );
+
+        let error = quote.at_rate(&FeeRate::PerKb("0.001".to_owned()), 2).unwrap_err();
+        assert_eq!(error.code(), ErrorCode::InvalidAmount);
+    }
+}
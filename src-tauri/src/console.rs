@@ -0,0 +1,170 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/node-gui-x/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeSet;
+
+use node_gui_backend::messages::WalletId;
+use parity_scale_codec::DecodeAll;
+
+use crate::error::{AppError, ErrorCode};
+
+/// The `config-broadcast` setting of the console of every wallet as last set by the user,
+/// restored after the app builds a transaction through the console with broadcasting off.
+///
+/// The console cannot be asked for it, so it is the value of the last `config-broadcast`
+/// command the user ran, or on when there was none, as the consoles start.
+#[derive(Debug, Default)]
+pub struct BroadcastSettings {
+    disabled: BTreeSet<WalletId>,
+}
+
+impl BroadcastSettings {
+    pub fn is_enabled(&self, wallet_id: WalletId) -> bool {
+        !self.disabled.contains(&wallet_id)
+    }
+
+    /// The `config-broadcast` argument that puts the user's setting back.
+    pub fn restore_arg(&self, wallet_id: WalletId) -> &'static str {
+        if self.is_enabled(wallet_id) {
+            "yes"
+        } else {
+            "no"
+        }
+    }
+
+    /// Records the setting changed by a console command, if it is `config-broadcast`.
+    pub fn observe(&mut self, wallet_id: WalletId, command: &str) {
+        let mut words = command.split_whitespace();
+        if words.next() != Some("config-broadcast") {
+            return;
+        }
+        match words.next() {
+            Some("yes") => {
+                self.disabled.remove(&wallet_id);
+            }
+            Some("no") => {
+                self.disabled.insert(wallet_id);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Builds a wallet console command line, quoting the arguments that need it.
+///
+/// The console is used for the wallet features node_gui_backend has no request for.
+pub fn command_line(command: &str, args: &[&str]) -> String {
+    let mut line = command.to_owned();
+    for arg in args {
+        line.push(' ');
+        if !arg.is_empty() && arg.chars().all(|c| c.is_ascii_alphanumeric() || "-_.:/".contains(c))
+        {
+            line.push_str(arg);
+        } else {
+            line.push('"');
+            line.push_str(&arg.replace('\\', "\\\\").replace('"', "\\\""));
+            line.push('"');
+        }
+    }
+    line
+}
+
+/// Reads a string printed by the console, which may be plain or wrapped as `{"text": ...}`.
+pub fn text(value: &serde_json::Value) -> Option<&str> {
+    value.as_str().or_else(|| value.get("text").and_then(serde_json::Value::as_str))
+}
+
+/// Finds the value of type `T` a command printed hex encoded along with some explanation,
+/// returning it with its hex form.
+///
+/// Every word of the output is tried and exactly one must decode as a whole `T`, so the
+/// ids and amounts printed around the data are never taken for it.
+pub fn printed_data<T: DecodeAll>(output: &str) -> Result<(String, T), AppError> {
+    let mut found = output.split_whitespace().filter_map(|word| {
+        let data = hex::decode(word).ok()?;
+        let value = T::decode_all(&mut data.as_slice()).ok()?;
+        Some((word.to_owned(), value))
+    });
+
+    match (found.next(), found.next()) {
+        (Some(data), None) => Ok(data),
+        (None, _) => Err(AppError::new(
+            ErrorCode::Wallet,
+            format!("Unexpected console output: {output}"),
+        )),
+        (Some(_), Some(_)) => Err(AppError::new(
+            ErrorCode::Wallet,
+            format!("Ambiguous console output: {output}"),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::chain::{SignedTransaction, Transaction};
+    use parity_scale_codec::Encode;
+
+    use super::*;
+
+    fn transaction_hex(flags: u128) -> String {
+        let transaction = Transaction::new(flags, Vec::new(), Vec::new()).unwrap();
+        hex::encode(SignedTransaction::new(transaction, Vec::new()).unwrap().encode())
+    }
+
+    #[test]
+    fn printed_data_skips_ids_and_words() {
+        let transaction = transaction_hex(0);
+        let output = format!(
+            "Transaction {} is ready to be added:\n\n{transaction}\n\nfee 0.5",
+            "ab".repeat(32)
+        );
+
+        let (hex, _) = printed_data::<SignedTransaction>(&output).unwrap();
+        assert_eq!(hex, transaction);
+    }
+
+    #[test]
+    fn printed_data_must_be_unique() {
+        let output = format!("{} {}", transaction_hex(0), transaction_hex(1));
+        assert!(printed_data::<SignedTransaction>(&output).is_err());
+        assert!(printed_data::<SignedTransaction>("no data here").is_err());
+    }
+
+    #[test]
+    fn broadcast_setting_follows_the_console() {
+        let wallet_id = WalletId::new();
+        let mut settings = BroadcastSettings::default();
+        assert!(settings.is_enabled(wallet_id));
+
+        settings.observe(wallet_id, "config-broadcast no");
+        assert!(!settings.is_enabled(wallet_id));
+        settings.observe(wallet_id, "account-balance");
+        assert!(!settings.is_enabled(wallet_id));
+        settings.observe(wallet_id, "  config-broadcast   yes ");
+        assert!(settings.is_enabled(wallet_id));
+    }
+
+    #[test]
+    fn broadcast_setting_is_restored_per_wallet() {
+        let wallet_id = WalletId::new();
+        let other_wallet_id = WalletId::new();
+        let mut settings = BroadcastSettings::default();
+        assert_eq!(settings.restore_arg(wallet_id), "yes");
+
+        settings.observe(wallet_id, "config-broadcast no");
+        assert_eq!(settings.restore_arg(wallet_id), "no");
+        assert_eq!(settings.restore_arg(other_wallet_id), "yes");
+    }
+}
@@ -0,0 +1,308 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/node-gui-x/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use node_gui_backend::{error::BackendError, messages::WalletId, AccountId};
+use serde::Serialize;
+
+/// Stable, machine readable error kinds the UI can react to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ErrorCode {
+    NodeNotInitialized,
+    NodeAlreadyInitialized,
+    NodeBusy,
+    InvalidInput,
+    InvalidAddress,
+    InvalidAmount,
+    InsufficientFunds,
+    WalletLocked,
+    UnknownWallet,
+    UnknownAccount,
+    Wallet,
+    Unsupported,
+    Storage,
+    Backend,
+}
+
+/// Error returned by the commands and emitted on the "Error" event.
+#[derive(Debug, Clone, Serialize)]
+pub struct AppError {
+    code: ErrorCode,
+    event: Option<String>,
+    wallet_id: Option<WalletId>,
+    account_id: Option<AccountId>,
+    message: String,
+}
+
+impl AppError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        AppError {
+            code,
+            event: None,
+            wallet_id: None,
+            account_id: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::InvalidInput, message)
+    }
+
+    pub fn node_not_initialized() -> Self {
+        Self::new(ErrorCode::NodeNotInitialized, "Node is not initialized")
+    }
+
+    pub fn node_busy() -> Self {
+        Self::new(ErrorCode::NodeBusy, "Node is starting or stopping")
+    }
+
+    pub fn code(&self) -> ErrorCode {
+        self.code
+    }
+
+    /// Sets the name of the command or event the error originates from.
+    pub fn with_event(mut self, event: &str) -> Self {
+        self.event = Some(event.to_owned());
+        self
+    }
+
+    /// Fills in the wallet and account ids, unless the error already carries them.
+    pub fn with_context(
+        mut self,
+        wallet_id: Option<WalletId>,
+        account_id: Option<AccountId>,
+    ) -> Self {
+        self.wallet_id = self.wallet_id.or(wallet_id);
+        self.account_id = self.account_id.or(account_id);
+        self
+    }
+}
+
+impl std::fmt::Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.message.fmt(f)
+    }
+}
+
+/// Fragments of the wallet error messages the UI handles specially, lowercased.
+///
+/// The wallet errors only reach the GUI backend as strings, so they have to be told
+/// apart by their messages; the tests below pin the messages this relies on.
+const WALLET_LOCKED_MESSAGES: &[&str] = &["wallet is locked", "wallet locked"];
+const INSUFFICIENT_FUNDS_MESSAGES: &[&str] = &["not enough funds", "not enough utxos"];
+
+fn wallet_error_code(wallet_error: &str) -> ErrorCode {
+    let wallet_error = wallet_error.to_lowercase();
+    let matches = |messages: &[&str]| messages.iter().any(|m| wallet_error.contains(m));
+    if matches(WALLET_LOCKED_MESSAGES) {
+        ErrorCode::WalletLocked
+    } else if matches(INSUFFICIENT_FUNDS_MESSAGES) {
+        ErrorCode::InsufficientFunds
+    } else {
+        ErrorCode::Wallet
+    }
+}
+
+/// Why the node refused a submitted transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum RejectionReason {
+    /// The fee is below the relay minimum or the current mempool threshold
+    FeeTooLow,
+    /// Already in the mempool, or spends what a transaction there spends
+    Conflict,
+    MempoolFull,
+    /// Spends outputs the node does not know of
+    Orphan,
+    /// Breaks another consensus or mempool rule
+    Invalid,
+}
+
+/// Fragments of the mempool and p2p error messages the node refuses a transaction
+/// with, lowercased, the more specific ones first.
+const REJECTION_MESSAGES: &[(&str, RejectionReason)] = &[
+    ("fee threshold not met", RejectionReason::FeeTooLow),
+    ("lower than minimum relay fee", RejectionReason::FeeTooLow),
+    ("insufficient fee", RejectionReason::FeeTooLow),
+    ("conflicts with", RejectionReason::Conflict),
+    ("already exists in the mempool", RejectionReason::Conflict),
+    ("mempool is full", RejectionReason::MempoolFull),
+    ("orphan", RejectionReason::Orphan),
+    ("mempool policy error", RejectionReason::Invalid),
+    ("mempool error", RejectionReason::Invalid),
+    ("transaction validation", RejectionReason::Invalid),
+    ("p2p error", RejectionReason::Invalid),
+];
+
+/// A transaction the node refused, as opposed to one that never reached it.
+#[derive(Debug, Clone, Serialize)]
+pub struct Rejection {
+    reason: RejectionReason,
+    message: String,
+}
+
+impl Rejection {
+    /// The rejection an error of submitting a transaction reports, `None` for any other
+    /// error.
+    pub fn from_error(error: &AppError) -> Option<Self> {
+        if !matches!(error.code, ErrorCode::Wallet | ErrorCode::Backend) {
+            return None;
+        }
+        let message = error.message.to_lowercase();
+        let (_, reason) =
+            REJECTION_MESSAGES.iter().find(|(fragment, _)| message.contains(fragment))?;
+        Some(Rejection {
+            reason: *reason,
+            message: error.message.clone(),
+        })
+    }
+
+    pub fn reason(&self) -> RejectionReason {
+        self.reason
+    }
+}
+
+impl From<BackendError> for AppError {
+    fn from(error: BackendError) -> Self {
+        let message = error.to_string();
+        match error {
+            BackendError::WalletError(wallet_error) => {
+                AppError::new(wallet_error_code(&wallet_error), message)
+            }
+            BackendError::UnknownWalletIndex(wallet_id) => {
+                AppError::new(ErrorCode::UnknownWallet, message).with_context(Some(wallet_id), None)
+            }
+            BackendError::UnknownAccountIndex(wallet_id, account_id) => {
+                AppError::new(ErrorCode::UnknownAccount, message)
+                    .with_context(Some(wallet_id), Some(account_id))
+            }
+            BackendError::AddressError(_) => AppError::new(ErrorCode::InvalidAddress, message),
+            BackendError::InvalidAmount(_) => AppError::new(ErrorCode::InvalidAmount, message),
+            _ => AppError::new(ErrorCode::Backend, message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_wallet_error(message: &str) -> AppError {
+        AppError::from(BackendError::WalletError(message.to_owned()))
+    }
+
+    #[test]
+    fn wallet_errors_are_told_apart_by_message() {
+        assert_eq!(
+            from_wallet_error("Wallet is locked").code(),
+            ErrorCode::WalletLocked
+        );
+        assert_eq!(
+            from_wallet_error("Coin selection error: Not enough funds got: 1, requested: 2").code(),
+            ErrorCode::InsufficientFunds
+        );
+        assert_eq!(
+            from_wallet_error("Not enough UTXOs amount: 1, required: 2").code(),
+            ErrorCode::InsufficientFunds
+        );
+        assert_eq!(
+            from_wallet_error("Invalid mnemonic: bad checksum").code(),
+            ErrorCode::Wallet
+        );
+        // Locked outputs are not a locked wallet
+        assert_eq!(
+            from_wallet_error("Cannot spend a locked UTXO").code(),
+            ErrorCode::Wallet
+        );
+    }
+
+    #[test]
+    fn message_is_kept() {
+        let error = from_wallet_error("Wallet is locked");
+        assert_eq!(
+            error.to_string(),
+            BackendError::WalletError("Wallet is locked".to_owned()).to_string()
+        );
+        assert_eq!(error.event, None);
+        assert_eq!(error.wallet_id, None);
+    }
+
+    #[test]
+    fn backend_errors_map_to_their_codes() {
+        let wallet_id = WalletId::new();
+        let error = AppError::from(BackendError::UnknownWalletIndex(wallet_id));
+        assert_eq!(error.code(), ErrorCode::UnknownWallet);
+        assert_eq!(error.wallet_id, Some(wallet_id));
+
+        assert_eq!(
+            AppError::from(BackendError::AddressError("bad".to_owned())).code(),
+            ErrorCode::InvalidAddress
+        );
+        assert_eq!(
+            AppError::from(BackendError::InvalidAmount("bad".to_owned())).code(),
+            ErrorCode::InvalidAmount
+        );
+        assert_eq!(
+            AppError::from(BackendError::InvalidConsoleCommand("bad".to_owned())).code(),
+            ErrorCode::Backend
+        );
+    }
+
+    #[test]
+    fn context_does_not_override_the_error_ids() {
+        let wallet_id = WalletId::new();
+        let error = AppError::from(BackendError::UnknownWalletIndex(wallet_id))
+            .with_context(Some(WalletId::new()), None)
+            .with_event("OpenWallet");
+        assert_eq!(error.wallet_id, Some(wallet_id));
+        assert_eq!(error.event.as_deref(), Some("OpenWallet"));
+    }
+
+    #[test]
+    fn node_rejections_are_told_apart_from_other_errors() {
+        let rejection = |message: &str| {
+            Rejection::from_error(&from_wallet_error(message)).map(|rejection| rejection.reason())
+        };
+        assert_eq!(
+            rejection("Mempool error: Mempool policy error: Rolling fee threshold not met"),
+            Some(RejectionReason::FeeTooLow)
+        );
+        assert_eq!(
+            rejection("Mempool policy error: Transaction conflicts with another, irreplaceable transaction."),
+            Some(RejectionReason::Conflict)
+        );
+        assert_eq!(
+            rejection("Mempool error: Mempool is full"),
+            Some(RejectionReason::MempoolFull)
+        );
+        assert_eq!(
+            rejection("Orphan transaction error: missing inputs"),
+            Some(RejectionReason::Orphan)
+        );
+        assert_eq!(
+            rejection("P2P error: Transaction validation failed"),
+            Some(RejectionReason::Invalid)
+        );
+
+        // The transaction never reached the node
+        assert_eq!(rejection("Wallet is locked"), None);
+        assert_eq!(rejection("Node RPC connection refused"), None);
+        let unknown_wallet = AppError::from(BackendError::UnknownWalletIndex(WalletId::new()));
+        assert!(Rejection::from_error(&unknown_wallet).is_none());
+
+        let message = "Mempool error: Mempool is full";
+        let rejection = Rejection::from_error(&from_wallet_error(message)).unwrap();
+        assert_eq!(rejection.message, from_wallet_error(message).to_string());
+    }
+}
@@ -0,0 +1,313 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/node-gui-x/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use chainstate::ChainInfo;
+use common::{
+    chain::GenBlock,
+    primitives::{BlockHeight, Id},
+};
+use node_gui_backend::{messages::WalletId, AccountId};
+use serde::Serialize;
+
+use crate::result::{
+    BalanceResult, DelegationsBalanceResult, StakingBalanceResult, TransactionListResult,
+    WalletBestBlockResult,
+};
+
+/// Latest value of every state event emitted to the UI, so that a window that was
+/// reloaded or mounted late can hydrate itself without waiting for the next change.
+///
+/// P2p events are not kept: they describe changes of the peer list, not its state.
+#[derive(Debug, Default)]
+pub struct EventCache {
+    chain_info: Option<ChainInfo>,
+    wallet_best_blocks: BTreeMap<WalletId, WalletBestBlockResult>,
+    balances: BTreeMap<(WalletId, AccountId), BalanceResult>,
+    staking_balances: BTreeMap<(WalletId, AccountId), StakingBalanceResult>,
+    delegations_balances: BTreeMap<(WalletId, AccountId), DelegationsBalanceResult>,
+    transaction_lists: BTreeMap<(WalletId, AccountId), TransactionListResult>,
+}
+
+/// Data of the accounts fetched on demand, kept until the backend tells it changed.
+#[derive(Debug)]
+pub struct AccountCache<T> {
+    entries: BTreeMap<(WalletId, AccountId), T>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EventSnapshot {
+    chain_info: Option<ChainInfo>,
+    wallet_best_blocks: Vec<WalletBestBlockResult>,
+    balances: Vec<BalanceResult>,
+    staking_balances: Vec<StakingBalanceResult>,
+    delegations_balances: Vec<DelegationsBalanceResult>,
+    transaction_lists: Vec<TransactionListResult>,
+}
+
+impl EventCache {
+    pub fn set_chain_info(&mut self, chain_info: ChainInfo) {
+        self.chain_info = Some(chain_info);
+    }
+
+    pub fn set_wallet_best_block(&mut self, wallet_id: WalletId, result: WalletBestBlockResult) {
+        self.wallet_best_blocks.insert(wallet_id, result);
+    }
+
+    pub fn set_balance(
+        &mut self,
+        wallet_id: WalletId,
+        account_id: AccountId,
+        result: BalanceResult,
+    ) {
+        self.balances.insert((wallet_id, account_id), result);
+    }
+
+    pub fn balance_mut(
+        &mut self,
+        wallet_id: WalletId,
+        account_id: AccountId,
+    ) -> Option<&mut BalanceResult> {
+        self.balances.get_mut(&(wallet_id, account_id))
+    }
+
+    pub fn set_staking_balance(
+        &mut self,
+        wallet_id: WalletId,
+        account_id: AccountId,
+        result: StakingBalanceResult,
+    ) {
+        self.staking_balances.insert((wallet_id, account_id), result);
+    }
+
+    pub fn set_delegations_balance(
+        &mut self,
+        wallet_id: WalletId,
+        account_id: AccountId,
+        result: DelegationsBalanceResult,
+    ) {
+        self.delegations_balances.insert((wallet_id, account_id), result);
+    }
+
+    pub fn set_transaction_list(
+        &mut self,
+        wallet_id: WalletId,
+        account_id: AccountId,
+        result: TransactionListResult,
+    ) {
+        self.transaction_lists.insert((wallet_id, account_id), result);
+    }
+
+    /// Position of the transaction list page last sent to the UI for an account.
+    pub fn transaction_list_skip(
+        &self,
+        wallet_id: WalletId,
+        account_id: AccountId,
+    ) -> Option<usize> {
+        self.transaction_lists
+            .get(&(wallet_id, account_id))
+            .map(TransactionListResult::skip)
+    }
+
+    /// Best block of the node, as last reported in `ChainInfo`.
+    pub fn best_block(&self) -> Option<(BlockHeight, Id<GenBlock>)> {
+        self.chain_info
+            .as_ref()
+            .map(|chain_info| (chain_info.best_block_height, chain_info.best_block_id))
+    }
+
+    pub fn wallet_best_block_height(&self, wallet_id: WalletId) -> Option<BlockHeight> {
+        self.wallet_best_blocks.get(&wallet_id).map(WalletBestBlockResult::height)
+    }
+
+    /// Accounts of the open wallets, each of them reports its balance once loaded.
+    pub fn accounts(&self) -> Vec<(WalletId, AccountId)> {
+        self.balances.keys().copied().collect()
+    }
+
+    /// Drops everything known about a wallet once it has been closed.
+    pub fn remove_wallet(&mut self, wallet_id: WalletId) {
+        self.wallet_best_blocks.remove(&wallet_id);
+        self.balances.retain(|(id, _), _| *id != wallet_id);
+        self.staking_balances.retain(|(id, _), _| *id != wallet_id);
+        self.delegations_balances.retain(|(id, _), _| *id != wallet_id);
+        self.transaction_lists.retain(|(id, _), _| *id != wallet_id);
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn snapshot(&self) -> EventSnapshot {
+        EventSnapshot {
+            chain_info: self.chain_info.clone(),
+            wallet_best_blocks: self.wallet_best_blocks.values().cloned().collect(),
+            balances: self.balances.values().cloned().collect(),
+            staking_balances: self.staking_balances.values().cloned().collect(),
+            delegations_balances: self.delegations_balances.values().cloned().collect(),
+            transaction_lists: self.transaction_lists.values().cloned().collect(),
+        }
+    }
+}
+
+impl<T> Default for AccountCache<T> {
+    fn default() -> Self {
+        AccountCache {
+            entries: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T> AccountCache<T> {
+    pub fn get(&self, wallet_id: WalletId, account_id: AccountId) -> Option<&T> {
+        self.entries.get(&(wallet_id, account_id))
+    }
+
+    pub fn insert(&mut self, wallet_id: WalletId, account_id: AccountId, value: T) {
+        self.entries.insert((wallet_id, account_id), value);
+    }
+
+    pub fn remove_account(&mut self, wallet_id: WalletId, account_id: AccountId) {
+        self.entries.remove(&(wallet_id, account_id));
+    }
+
+    pub fn remove_wallet(&mut self, wallet_id: WalletId) {
+        self.entries.retain(|(id, _), _| *id != wallet_id);
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::primitives::H256;
+
+    use super::*;
+
+    fn account(index: u32) -> AccountId {
+        serde_json::from_value(serde_json::json!(index)).unwrap()
+    }
+
+    fn best_block(wallet_id: WalletId, height: u64) -> WalletBestBlockResult {
+        WalletBestBlockResult::new(
+            wallet_id,
+            (
+                Id::new(H256::from_low_u64_be(height)),
+                BlockHeight::new(height),
+            ),
+        )
+    }
+
+    fn staking_balance(wallet_id: WalletId, account_id: AccountId) -> StakingBalanceResult {
+        StakingBalanceResult::new(wallet_id, account_id, BTreeMap::new())
+    }
+
+    #[test]
+    fn updates_replace_the_previous_value() {
+        let wallet_id = WalletId::new();
+        let mut cache = EventCache::default();
+        cache.set_wallet_best_block(wallet_id, best_block(wallet_id, 5));
+        cache.set_wallet_best_block(wallet_id, best_block(wallet_id, 7));
+        cache.set_staking_balance(
+            wallet_id,
+            account(0),
+            staking_balance(wallet_id, account(0)),
+        );
+        cache.set_staking_balance(
+            wallet_id,
+            account(0),
+            staking_balance(wallet_id, account(0)),
+        );
+        cache.set_staking_balance(
+            wallet_id,
+            account(1),
+            staking_balance(wallet_id, account(1)),
+        );
+
+        assert_eq!(
+            cache.wallet_best_block_height(wallet_id),
+            Some(BlockHeight::new(7))
+        );
+        let snapshot = cache.snapshot();
+        assert_eq!(snapshot.wallet_best_blocks.len(), 1);
+        assert_eq!(snapshot.wallet_best_blocks[0].height(), BlockHeight::new(7));
+        assert_eq!(snapshot.staking_balances.len(), 2);
+        assert!(snapshot.chain_info.is_none());
+        assert_eq!(cache.best_block(), None);
+    }
+
+    #[test]
+    fn snapshots_do_not_follow_later_updates() {
+        let wallet_id = WalletId::new();
+        let mut cache = EventCache::default();
+        cache.set_wallet_best_block(wallet_id, best_block(wallet_id, 5));
+        let snapshot = cache.snapshot();
+
+        cache.set_wallet_best_block(wallet_id, best_block(wallet_id, 6));
+        assert_eq!(snapshot.wallet_best_blocks[0].height(), BlockHeight::new(5));
+        assert_eq!(
+            cache.snapshot().wallet_best_blocks[0].height(),
+            BlockHeight::new(6)
+        );
+    }
+
+    #[test]
+    fn closed_wallets_and_stopped_nodes_are_forgotten() {
+        let wallet_id = WalletId::new();
+        let other_wallet_id = WalletId::new();
+        let mut cache = EventCache::default();
+        for id in [wallet_id, other_wallet_id] {
+            cache.set_wallet_best_block(id, best_block(id, 1));
+            cache.set_staking_balance(id, account(0), staking_balance(id, account(0)));
+        }
+
+        cache.remove_wallet(wallet_id);
+        assert_eq!(cache.wallet_best_block_height(wallet_id), None);
+        assert_eq!(
+            cache.wallet_best_block_height(other_wallet_id),
+            Some(BlockHeight::new(1))
+        );
+        assert_eq!(cache.snapshot().staking_balances.len(), 1);
+
+        cache.clear();
+        let snapshot = cache.snapshot();
+        assert!(snapshot.wallet_best_blocks.is_empty());
+        assert!(snapshot.staking_balances.is_empty());
+    }
+
+    #[test]
+    fn account_cache_forgets_accounts_and_wallets() {
+        let wallet_id = WalletId::new();
+        let other_wallet_id = WalletId::new();
+        let mut cache = AccountCache::default();
+        cache.insert(wallet_id, account(0), "first");
+        cache.insert(wallet_id, account(0), "second");
+        cache.insert(wallet_id, account(1), "other account");
+        cache.insert(other_wallet_id, account(0), "other wallet");
+        assert_eq!(cache.get(wallet_id, account(0)), Some(&"second"));
+
+        cache.remove_account(wallet_id, account(1));
+        assert_eq!(cache.get(wallet_id, account(1)), None);
+        cache.remove_wallet(wallet_id);
+        assert_eq!(cache.get(wallet_id, account(0)), None);
+        assert_eq!(
+            cache.get(other_wallet_id, account(0)),
+            Some(&"other wallet")
+        );
+    }
+}
@@ -0,0 +1,225 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/node-gui-x/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common::primitives::Amount;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    error::{AppError, ErrorCode},
+    utxo::{coin_output, UtxoEntry},
+};
+
+/// Fee rate to build a transaction with, relative to the rate the mempool currently asks.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FeeRate {
+    /// Half the mempool rate, which a busy mempool may refuse
+    Low,
+    /// The mempool rate, as used by the backend
+    #[default]
+    Normal,
+    /// Twice the mempool rate
+    High,
+    /// Explicit rate in coins per 1000 bytes
+    PerKb(String),
+}
+
+/// What sending an amount would cost, computed from a transaction built but not submitted.
+#[derive(Debug, Clone, Serialize)]
+pub struct FeeEstimate {
+    /// Outpoints of the selected inputs
+    inputs: Vec<Value>,
+    payment: Amount,
+    change: Amount,
+    fee: Amount,
+    /// Amount leaving the account, the payment plus the fee
+    total_debit: Amount,
+}
+
+impl FeeEstimate {
+    /// Computes the estimate from a serialized `TransactionResult` and the unspent
+    /// outputs of the account, which provide the values of the inputs.
+    pub fn new(
+        transaction: &Value,
+        utxos: &[UtxoEntry],
+        recipient: &str,
+    ) -> Result<Self, AppError> {
+        let tx = transaction
+            .get("serialized_tx")
+            .and_then(|tx| tx.get("V1"))
+            .ok_or_else(|| invalid_transaction("unknown transaction version"))?;
+        let inputs = tx
+            .get("inputs")
+            .and_then(Value::as_array)
+            .ok_or_else(|| invalid_transaction("missing inputs"))?;
+        let outputs = tx
+            .get("outputs")
+            .and_then(Value::as_array)
+            .ok_or_else(|| invalid_transaction("missing outputs"))?;
+
+        let inputs =
+            inputs.iter().filter_map(|input| input.get("Utxo").cloned()).collect::<Vec<_>>();
+
+        let mut input_total = Amount::ZERO;
+        for outpoint in &inputs {
+            let value = utxos
+                .iter()
+                .find(|utxo| utxo.outpoint() == outpoint)
+                .and_then(UtxoEntry::amount)
+                .ok_or_else(|| {
+                    AppError::new(
+                        ErrorCode::Wallet,
+                        "Input is not an unspent output of the account",
+                    )
+                })?;
+            input_total = checked_add(input_total, value)?;
+        }
+
+        let mut payment = Amount::ZERO;
+        let mut change = Amount::ZERO;
+        for (value, destination) in outputs.iter().filter_map(coin_output) {
+            if destination == recipient {
+                payment = checked_add(payment, value)?;
+            } else {
+                change = checked_add(change, value)?;
+            }
+        }
+
+        let fee = ((input_total - payment).and_then(|rest| rest - change))
+            .ok_or_else(|| invalid_transaction("outputs exceed inputs"))?;
+        let total_debit = checked_add(payment, fee)?;
+
+        Ok(FeeEstimate {
+            inputs,
+            payment,
+            change,
+            fee,
+            total_debit,
+        })
+    }
+
+    /// Estimate of a transaction whose inputs and outputs were chosen by the app.
+    pub fn from_parts(
+        inputs: Vec<Value>,
+        payment: Amount,
+        change: Amount,
+        fee: Amount,
+    ) -> Result<Self, AppError> {
+        Ok(FeeEstimate {
+            inputs,
+            payment,
+            change,
+            fee,
+            total_debit: checked_add(payment, fee)?,
+        })
+    }
+
+    pub fn fee(&self) -> Amount {
+        self.fee
+    }
+}
+
+fn checked_add(a: Amount, b: Amount) -> Result<Amount, AppError> {
+    (a + b).ok_or_else(|| AppError::new(ErrorCode::InvalidAmount, "Amount overflow"))
+}
+
+fn invalid_transaction(reason: &str) -> AppError {
+    AppError::new(ErrorCode::Backend, format!("Invalid transaction: {reason}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn outpoint(index: u64) -> Value {
+        json!({ "id": { "Transaction": format!("{index:064x}") }, "index": index })
+    }
+
+    fn transfer(atoms: u128, address: &str) -> Value {
+        json!({ "Transfer": [{ "Coin": { "atoms": atoms.to_string() } }, address] })
+    }
+
+    fn utxo(index: u64, atoms: u128) -> UtxoEntry {
+        UtxoEntry::from_json(
+            &json!({ "outpoint": outpoint(index), "output": transfer(atoms, "own") }),
+            false,
+        )
+        .unwrap()
+    }
+
+    fn transaction(inputs: &[u64], outputs: &[Value]) -> Value {
+        let inputs = inputs.iter().map(|index| json!({ "Utxo": outpoint(*index) }));
+        json!({
+            "serialized_tx": {
+                "V1": { "inputs": inputs.collect::<Vec<_>>(), "outputs": outputs },
+            },
+        })
+    }
+
+    #[test]
+    fn payment_change_and_fee_come_from_the_built_transaction() {
+        let utxos = [utxo(1, 100), utxo(2, 50), utxo(3, 70)];
+        let transaction = transaction(&[1, 2], &[transfer(120, "them"), transfer(25, "own")]);
+
+        let estimate = FeeEstimate::new(&transaction, &utxos, "them").unwrap();
+
+        assert_eq!(estimate.inputs, [outpoint(1), outpoint(2)]);
+        assert_eq!(estimate.payment, Amount::from_atoms(120));
+        assert_eq!(estimate.change, Amount::from_atoms(25));
+        assert_eq!(estimate.fee(), Amount::from_atoms(5));
+        assert_eq!(estimate.total_debit, Amount::from_atoms(125));
+    }
+
+    #[test]
+    fn foreign_inputs_and_overspending_are_rejected() {
+        let utxos = [utxo(1, 100)];
+
+        let foreign = transaction(&[9], &[transfer(10, "them")]);
+        assert_eq!(
+            FeeEstimate::new(&foreign, &utxos, "them").unwrap_err().code(),
+            ErrorCode::Wallet
+        );
+
+        let overspending = transaction(&[1], &[transfer(101, "them")]);
+        assert_eq!(
+            FeeEstimate::new(&overspending, &utxos, "them").unwrap_err().code(),
+            ErrorCode::Backend
+        );
+    }
+
+    #[test]
+    fn estimate_from_parts_debits_the_payment_and_the_fee() {
+        let estimate = FeeEstimate::from_parts(
+            vec![outpoint(1)],
+            Amount::from_atoms(80),
+            Amount::from_atoms(15),
+            Amount::from_atoms(5),
+        )
+        .unwrap();
+
+        assert_eq!(estimate.change, Amount::from_atoms(15));
+        assert_eq!(estimate.total_debit, Amount::from_atoms(85));
+
+        let overflow = FeeEstimate::from_parts(
+            Vec::new(),
+            Amount::from_atoms(u128::MAX),
+            Amount::ZERO,
+            Amount::from_atoms(1),
+        );
+        assert_eq!(overflow.unwrap_err().code(), ErrorCode::InvalidAmount);
+    }
+}
@@ -0,0 +1,504 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/node-gui-x/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{cmp::Reverse, collections::BTreeMap};
+
+use common::{
+    address::Address,
+    chain::{ChainConfig, Destination, OutPointSourceId, Transaction, TxInput, TxOutput},
+    primitives::{Amount, Id},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{AppError, ErrorCode};
+
+const DEFAULT_PAGE_SIZE: usize = 20;
+const MAX_PAGE_SIZE: usize = 100;
+
+/// Transactions of an account history by their id as listed in it, needed to match a
+/// `HistoryQuery::address`.
+pub type Transactions = BTreeMap<String, Transaction>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TxKind {
+    Received,
+    Sent,
+    Redeposit,
+    Other,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum SortOrder {
+    /// Transactions not confirmed yet come first
+    #[default]
+    NewestFirst,
+    OldestFirst,
+    LargestFirst,
+    SmallestFirst,
+}
+
+/// Filters, sorting and page of a transaction history query, every filter is optional.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct HistoryQuery {
+    /// Kinds of transactions to keep, all of them if empty
+    pub kinds: Vec<TxKind>,
+    /// Block timestamps in seconds, inclusive; unconfirmed transactions have none and
+    /// are left out by either bound
+    pub from_timestamp: Option<u64>,
+    pub to_timestamp: Option<u64>,
+    /// Coin amounts, inclusive
+    pub min_amount: Option<String>,
+    pub max_amount: Option<String>,
+    /// Full or partial transaction id
+    pub txid: Option<String>,
+    /// Address the transactions pay to or spend from; only the spent outputs created by
+    /// transactions of the same history are known
+    pub address: Option<String>,
+    pub sort: SortOrder,
+    pub skip: usize,
+    pub count: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryPage {
+    /// Transactions of the account
+    total: usize,
+    /// Transactions matching the filters, of which `txs` is a page
+    matching: usize,
+    skip: usize,
+    txs: Vec<Value>,
+    /// Sums over all the matching transactions
+    received: Amount,
+    sent: Amount,
+}
+
+struct Entry {
+    tx: Value,
+    kind: TxKind,
+    amount: Option<Amount>,
+    timestamp: Option<u64>,
+}
+
+impl Entry {
+    fn from_json(tx: Value) -> Result<Self, AppError> {
+        let tx_type = tx.get("tx_type").ok_or_else(|| {
+            AppError::new(ErrorCode::Backend, format!("Unexpected transaction: {tx}"))
+        })?;
+        let (kind, details) = match tx_type {
+            Value::String(kind) => (kind.as_str(), None),
+            Value::Object(fields) => match fields.iter().next() {
+                Some((kind, details)) => (kind.as_str(), Some(details)),
+                None => ("Other", None),
+            },
+            _ => ("Other", None),
+        };
+        let kind = match kind {
+            "Received" => TxKind::Received,
+            "Sent" => TxKind::Sent,
+            "Redeposit" => TxKind::Redeposit,
+            _ => TxKind::Other,
+        };
+        let amount = details
+            .and_then(|details| details.get("amount")?.get("atoms"))
+            .and_then(|atoms| match atoms {
+                Value::String(atoms) => atoms.parse().ok(),
+                atoms => atoms.as_u64().map(u128::from),
+            })
+            .map(Amount::from_atoms);
+        let timestamp = tx
+            .get("timestamp")
+            .and_then(|timestamp| timestamp.get("timestamp").unwrap_or(timestamp).as_u64());
+
+        Ok(Entry {
+            tx,
+            kind,
+            amount,
+            timestamp,
+        })
+    }
+
+    fn txid(&self) -> &str {
+        self.tx.get("txid").and_then(Value::as_str).unwrap_or_default()
+    }
+
+    /// Whether one of the outputs of the transaction, or one of the outputs it spends,
+    /// belongs to `destination`.
+    fn involves(&self, transactions: &Transactions, destination: &Destination) -> bool {
+        let Some(transaction) = transactions.get(self.txid()) else {
+            return false;
+        };
+        let pays = |output: &TxOutput| output_destination(output) == Some(destination);
+
+        let spends = |input: &TxInput| match input {
+            TxInput::Utxo(outpoint) => match outpoint.source_id() {
+                OutPointSourceId::Transaction(id) => id_key(&id)
+                    .and_then(|id| transactions.get(&id))
+                    .and_then(|spent| spent.outputs().get(outpoint.output_index() as usize))
+                    .is_some_and(pays),
+                OutPointSourceId::BlockReward(_) => false,
+            },
+            _ => false,
+        };
+
+        transaction.outputs().iter().any(pays) || transaction.inputs().iter().any(spends)
+    }
+}
+
+/// The id of a transaction as the history lists it.
+pub fn id_key(id: &Id<Transaction>) -> Option<String> {
+    serde_json::to_value(id).ok()?.as_str().map(str::to_owned)
+}
+
+fn output_destination(output: &TxOutput) -> Option<&Destination> {
+    match output {
+        TxOutput::Transfer(_, destination)
+        | TxOutput::LockThenTransfer(_, destination, _)
+        | TxOutput::ProduceBlockFromStake(destination, _)
+        | TxOutput::CreateDelegationId(destination, _)
+        | TxOutput::IssueNft(_, _, destination) => Some(destination),
+        _ => None,
+    }
+}
+
+/// Whether `value` is within `bound`, always when there is no bound.
+fn within<T: Copy>(value: Option<T>, bound: Option<T>, check: impl Fn(T, T) -> bool) -> bool {
+    match bound {
+        Some(bound) => value.is_some_and(|value| check(value, bound)),
+        None => true,
+    }
+}
+
+impl HistoryQuery {
+    /// Filters and sorts the whole history of an account, `txs` as listed by the
+    /// backend, and returns the requested page of it. `transactions` are only looked at
+    /// when filtering by address.
+    pub fn apply(
+        &self,
+        chain_config: &ChainConfig,
+        txs: Vec<Value>,
+        transactions: &Transactions,
+    ) -> Result<HistoryPage, AppError> {
+        let min_amount = parse_coins(chain_config, self.min_amount.as_deref())?;
+        let max_amount = parse_coins(chain_config, self.max_amount.as_deref())?;
+        let txid = self.txid.as_deref().map(str::to_lowercase);
+        let destination = self
+            .address
+            .as_deref()
+            .map(|address| {
+                Address::<Destination>::from_string(chain_config, address)
+                    .map(Address::into_object)
+                    .map_err(|e| AppError::new(ErrorCode::InvalidAddress, e.to_string()))
+            })
+            .transpose()?;
+
+        let total = txs.len();
+        let mut entries = txs.into_iter().map(Entry::from_json).collect::<Result<Vec<_>, _>>()?;
+
+        entries.retain(|entry| {
+            (self.kinds.is_empty() || self.kinds.contains(&entry.kind))
+                && within(entry.timestamp, self.from_timestamp, |t, from| t >= from)
+                && within(entry.timestamp, self.to_timestamp, |t, to| t <= to)
+                && within(entry.amount, min_amount, |a, min| a >= min)
+                && within(entry.amount, max_amount, |a, max| a <= max)
+                && match &txid {
+                    Some(txid) => entry.txid().to_lowercase().contains(txid),
+                    None => true,
+                }
+                && match &destination {
+                    Some(destination) => entry.involves(transactions, destination),
+                    None => true,
+                }
+        });
+
+        match self.sort {
+            SortOrder::NewestFirst => {
+                entries.sort_by_key(|entry| Reverse(entry.timestamp.unwrap_or(u64::MAX)))
+            }
+            SortOrder::OldestFirst => {
+                entries.sort_by_key(|entry| entry.timestamp.unwrap_or(u64::MAX))
+            }
+            SortOrder::LargestFirst => entries.sort_by_key(|entry| Reverse(entry.amount)),
+            SortOrder::SmallestFirst => entries.sort_by_key(|entry| entry.amount),
+        }
+
+        let sum = |kind: TxKind| {
+            entries
+                .iter()
+                .filter(|entry| entry.kind == kind)
+                .filter_map(|entry| entry.amount)
+                .try_fold(Amount::ZERO, |sum, amount| sum + amount)
+                .ok_or_else(|| AppError::new(ErrorCode::InvalidAmount, "Amount overflow"))
+        };
+        let received = sum(TxKind::Received)?;
+        let sent = sum(TxKind::Sent)?;
+
+        let count = self.count.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+        let matching = entries.len();
+        let txs = entries.into_iter().skip(self.skip).take(count).map(|entry| entry.tx).collect();
+
+        Ok(HistoryPage {
+            total,
+            matching,
+            skip: self.skip,
+            txs,
+            received,
+            sent,
+        })
+    }
+}
+
+fn parse_coins(
+    chain_config: &ChainConfig,
+    amount: Option<&str>,
+) -> Result<Option<Amount>, AppError> {
+    amount
+        .map(|amount| {
+            Amount::from_fixedpoint_str(amount, chain_config.coin_decimals()).ok_or_else(|| {
+                AppError::new(
+                    ErrorCode::InvalidAmount,
+                    format!("Invalid amount: {amount}"),
+                )
+            })
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{
+        address::pubkeyhash::PublicKeyHash,
+        chain::{config::create_unit_test_config, output_value::OutputValue},
+        primitives::Idable,
+    };
+    use serde_json::json;
+
+    use super::*;
+
+    fn coins(chain_config: &ChainConfig, amount: &str) -> Amount {
+        Amount::from_fixedpoint_str(amount, chain_config.coin_decimals()).unwrap()
+    }
+
+    fn tx(txid: &str, kind: &str, amount: Amount, timestamp: Option<u64>) -> Value {
+        let mut tx = json!({
+            "txid": txid,
+            "tx_type": { kind: { "amount": { "atoms": amount.into_atoms().to_string() } } },
+        });
+        if let Some(timestamp) = timestamp {
+            tx["timestamp"] = json!({ "timestamp": timestamp });
+        }
+        tx
+    }
+
+    fn history(chain_config: &ChainConfig) -> Vec<Value> {
+        vec![
+            tx("aa01", "Received", coins(chain_config, "10"), Some(100)),
+            tx("bb02", "Sent", coins(chain_config, "3"), Some(200)),
+            tx("cc03", "Received", coins(chain_config, "5"), Some(300)),
+            tx("dd04", "Sent", coins(chain_config, "1"), None),
+            json!({ "txid": "ee05", "tx_type": "Other", "timestamp": { "timestamp": 400 } }),
+        ]
+    }
+
+    fn txids(page: &HistoryPage) -> Vec<&str> {
+        page.txs.iter().map(|tx| tx["txid"].as_str().unwrap()).collect()
+    }
+
+    #[test]
+    fn newest_first_by_default_with_unconfirmed_on_top() {
+        let chain_config = create_unit_test_config();
+        let page = HistoryQuery::default()
+            .apply(&chain_config, history(&chain_config), &Transactions::new())
+            .unwrap();
+
+        assert_eq!(txids(&page), ["dd04", "ee05", "cc03", "bb02", "aa01"]);
+        assert_eq!(page.total, 5);
+        assert_eq!(page.matching, 5);
+        assert_eq!(page.received, coins(&chain_config, "15"));
+        assert_eq!(page.sent, coins(&chain_config, "4"));
+    }
+
+    #[test]
+    fn sorting_by_time_and_amount() {
+        let chain_config = create_unit_test_config();
+        let sorted = |sort| {
+            let query = HistoryQuery {
+                kinds: vec![TxKind::Received, TxKind::Sent],
+                sort,
+                ..Default::default()
+            };
+            txids(
+                &query
+                    .apply(&chain_config, history(&chain_config), &Transactions::new())
+                    .unwrap(),
+            )
+            .into_iter()
+            .map(str::to_owned)
+            .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            sorted(SortOrder::OldestFirst),
+            ["aa01", "bb02", "cc03", "dd04"]
+        );
+        assert_eq!(
+            sorted(SortOrder::LargestFirst),
+            ["aa01", "cc03", "bb02", "dd04"]
+        );
+        assert_eq!(
+            sorted(SortOrder::SmallestFirst),
+            ["dd04", "bb02", "cc03", "aa01"]
+        );
+    }
+
+    #[test]
+    fn filters_combine_and_totals_cover_the_matching_transactions() {
+        let chain_config = create_unit_test_config();
+        let query = HistoryQuery {
+            kinds: vec![TxKind::Received, TxKind::Sent],
+            from_timestamp: Some(150),
+            min_amount: Some("2".to_owned()),
+            ..Default::default()
+        };
+        let page = query
+            .apply(&chain_config, history(&chain_config), &Transactions::new())
+            .unwrap();
+
+        // The unconfirmed transaction has no timestamp and the 1 coin one is too small
+        assert_eq!(txids(&page), ["cc03", "bb02"]);
+        assert_eq!(page.total, 5);
+        assert_eq!(page.matching, 2);
+        assert_eq!(page.received, coins(&chain_config, "5"));
+        assert_eq!(page.sent, coins(&chain_config, "3"));
+    }
+
+    #[test]
+    fn txid_filter_is_a_case_insensitive_substring() {
+        let chain_config = create_unit_test_config();
+        let query = HistoryQuery {
+            txid: Some("B0".to_owned()),
+            ..Default::default()
+        };
+        let page = query
+            .apply(&chain_config, history(&chain_config), &Transactions::new())
+            .unwrap();
+
+        assert_eq!(txids(&page), ["bb02"]);
+    }
+
+    #[test]
+    fn paging_keeps_the_totals_of_every_match() {
+        let chain_config = create_unit_test_config();
+        let query = HistoryQuery {
+            skip: 1,
+            count: Some(2),
+            ..Default::default()
+        };
+        let page = query
+            .apply(&chain_config, history(&chain_config), &Transactions::new())
+            .unwrap();
+
+        assert_eq!(txids(&page), ["ee05", "cc03"]);
+        assert_eq!(page.skip, 1);
+        assert_eq!(page.matching, 5);
+        assert_eq!(page.received, coins(&chain_config, "15"));
+
+        let past_the_end = HistoryQuery {
+            skip: 10,
+            ..Default::default()
+        };
+        let page = past_the_end
+            .apply(&chain_config, history(&chain_config), &Transactions::new())
+            .unwrap();
+        assert!(page.txs.is_empty());
+        assert_eq!(page.matching, 5);
+    }
+
+    #[test]
+    fn invalid_amounts_are_rejected() {
+        let chain_config = create_unit_test_config();
+        let query = HistoryQuery {
+            max_amount: Some("lots".to_owned()),
+            ..Default::default()
+        };
+        let error = query
+            .apply(&chain_config, history(&chain_config), &Transactions::new())
+            .unwrap_err();
+
+        assert_eq!(error.code(), ErrorCode::InvalidAmount);
+    }
+
+    #[test]
+    fn address_filter_matches_what_is_paid_and_spent() {
+        let chain_config = create_unit_test_config();
+        let destination = |byte| Destination::PublicKeyHash(PublicKeyHash::repeat_byte(byte));
+        let address = |byte| Address::new(&chain_config, destination(byte)).unwrap().to_string();
+        let pay = |byte, amount| {
+            TxOutput::Transfer(
+                OutputValue::Coin(coins(&chain_config, amount)),
+                destination(byte),
+            )
+        };
+
+        let funding = Transaction::new(0, Vec::new(), vec![pay(1, "10"), pay(2, "5")]).unwrap();
+        let spending_input = TxInput::from_utxo(OutPointSourceId::Transaction(funding.get_id()), 0);
+        let spending = Transaction::new(0, vec![spending_input], vec![pay(3, "9")]).unwrap();
+        let other = Transaction::new(1, Vec::new(), vec![pay(3, "1")]).unwrap();
+
+        let mut transactions = Transactions::new();
+        let mut history = Vec::new();
+        for (transaction, timestamp) in [(funding, 100), (spending, 200), (other, 300)] {
+            let txid = id_key(&transaction.get_id()).unwrap();
+            history.push(tx(
+                &txid,
+                "Received",
+                coins(&chain_config, "1"),
+                Some(timestamp),
+            ));
+            transactions.insert(txid, transaction);
+        }
+        let matching = |byte| {
+            let query = HistoryQuery {
+                address: Some(address(byte)),
+                sort: SortOrder::OldestFirst,
+                ..Default::default()
+            };
+            let page = query.apply(&chain_config, history.clone(), &transactions).unwrap();
+            page.txs
+                .iter()
+                .map(|tx| tx["timestamp"]["timestamp"].as_u64().unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(matching(1), [100, 200]);
+        assert_eq!(matching(2), [100]);
+        assert_eq!(matching(3), [200, 300]);
+        assert!(matching(4).is_empty());
+    }
+
+    #[test]
+    fn invalid_addresses_are_rejected() {
+        let chain_config = create_unit_test_config();
+        let query = HistoryQuery {
+            address: Some("not an address".to_owned()),
+            ..Default::default()
+        };
+        let error = query
+            .apply(&chain_config, history(&chain_config), &Transactions::new())
+            .unwrap_err();
+        assert_eq!(error.code(), ErrorCode::InvalidAddress);
+    }
+}
@@ -13,43 +13,175 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::{path::Path, sync::Arc};
+use tokio::{sync::Mutex, task::JoinHandle};
 
 use common::chain::ChainConfig;
+mod address_book;
 mod commands;
+mod compose;
+mod console;
+mod error;
+mod event_cache;
+mod fee;
+mod history;
+mod lifecycle;
+mod mnemonic;
+mod network;
+mod nft;
+mod payout;
+mod qr_frames;
 mod request;
+mod request_tracker;
 mod result;
+mod settings;
+mod signing;
+mod storage;
+mod token;
+mod transaction_decode;
+mod transaction_file;
+mod utxo;
+mod wallet_registry;
 
+use error::AppError;
 use node_gui_backend::BackendSender;
 
-use tauri::Manager;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+/// State shared between the commands and the task listening to backend events
+#[derive(Debug)]
+struct EventState {
+    request_tracker: request_tracker::RequestTracker,
+    event_cache: event_cache::EventCache,
+    wallet_registry: wallet_registry::WalletRegistry,
+    token_infos: token::TokenInfoCache,
+    /// Outputs of the accounts, dropped when their balance changes
+    utxos: event_cache::AccountCache<Vec<utxo::UtxoEntry>>,
+    /// Whole transaction histories of the accounts, dropped when the backend refreshes
+    /// their transaction list
+    histories: event_cache::AccountCache<Vec<serde_json::Value>>,
+    /// Transactions fetched to filter the histories by address, which never change
+    transactions: history::Transactions,
+}
 
 struct AppState {
     backend_sender: Option<BackendSender>,
     chain_config: Option<Arc<ChainConfig>>,
-    app_handle: tauri::AppHandle,
+    node_state: lifecycle::NodeState,
+    listener_task: Option<JoinHandle<()>>,
+    network: Option<network::Network>,
+    event_state: Arc<Mutex<EventState>>,
+    settings: settings::SettingsStore,
+    address_book: address_book::AddressBook,
+    /// Held across the console commands of a transaction built through the console
+    console_broadcast: Arc<Mutex<console::BroadcastSettings>>,
+}
+
+impl AppState {
+    /// State of a freshly launched app, with the files kept in `config_dir` loaded.
+    fn new(config_dir: &Path) -> Self {
+        let event_state = EventState {
+            request_tracker: Default::default(),
+            event_cache: Default::default(),
+            wallet_registry: wallet_registry::WalletRegistry::load(
+                config_dir.join("recent_wallets.json"),
+            ),
+            token_infos: Default::default(),
+            utxos: Default::default(),
+            histories: Default::default(),
+            transactions: Default::default(),
+        };
+
+        AppState {
+            backend_sender: None,
+            chain_config: None,
+            node_state: lifecycle::NodeState::Uninitialized,
+            listener_task: None,
+            network: None,
+            event_state: Arc::new(Mutex::new(event_state)),
+            settings: settings::SettingsStore::load(config_dir.join("settings.json")),
+            address_book: address_book::AddressBook::load(config_dir.join("address_book.json")),
+            console_broadcast: Default::default(),
+        }
+    }
+
+    fn backend_sender(&self) -> Result<&BackendSender, AppError> {
+        self.backend_sender.as_ref().ok_or_else(AppError::node_not_initialized)
+    }
+
+    fn chain_config(&self) -> Result<&Arc<ChainConfig>, AppError> {
+        self.chain_config.as_ref().ok_or_else(AppError::node_not_initialized)
+    }
+
+    fn network(&self) -> Result<network::Network, AppError> {
+        self.network.ok_or_else(AppError::node_not_initialized)
+    }
+
+    fn set_node_state<R: Runtime>(
+        &mut self,
+        app_handle: &AppHandle<R>,
+        node_state: lifecycle::NodeState,
+    ) {
+        self.node_state = node_state;
+        app_handle.emit("NodeState", node_state).expect("Failed to emit node state");
+    }
 }
 
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_process::init())
         .setup(|app| {
-            app.manage(Mutex::new(AppState {
-                backend_sender: None,
-                chain_config: None,
-                app_handle: app.handle().clone(),
-            }));
+            let config_dir = app.path().app_config_dir()?;
+            app.manage(Mutex::new(AppState::new(&config_dir)));
             Ok(())
         })
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .invoke_handler(tauri::generate_handler![
             commands::initialize_node,
+            commands::restart_node,
+            commands::get_node_state,
+            commands::get_network,
+            commands::get_event_snapshot,
+            commands::get_settings,
+            commands::set_settings,
             commands::get_stake_pool_maturity_distance,
+            commands::generate_mnemonic,
+            commands::validate_mnemonic,
+            commands::list_open_wallets,
+            commands::select_wallet,
+            commands::list_recent_wallets,
+            commands::remove_recent_wallet,
             commands::add_create_wallet_wrapper,
             commands::add_open_wallet_wrapper,
+            commands::list_utxos,
+            commands::estimate_fee,
             commands::send_amount_wrapper,
+            commands::sweep_account_wrapper,
+            commands::get_token_info,
+            commands::send_tokens,
+            commands::issue_token,
+            commands::manage_token,
+            commands::issue_nft,
+            commands::list_nfts,
+            commands::transfer_nft,
+            commands::sign_message,
+            commands::verify_message,
+            commands::list_contacts,
+            commands::find_contacts,
+            commands::add_contact,
+            commands::update_contact,
+            commands::delete_contact,
+            commands::export_transaction_file,
+            commands::import_transaction_file,
+            commands::sign_transaction,
+            commands::decode_transaction,
+            commands::broadcast_transaction,
+            commands::query_transactions,
+            commands::encode_qr_frames,
+            commands::decode_qr_frames,
+            commands::batch_send_wrapper,
+            commands::preview_payout_csv,
             commands::new_address_wrapper,
             commands::update_encryption_wrapper,
             commands::close_wallet_wrapper,
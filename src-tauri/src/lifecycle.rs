@@ -0,0 +1,228 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/node-gui-x/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chainstate::ChainInfo;
+use common::time_getter::TimeGetter;
+use node_gui_backend::{messages::BackendRequest, WalletMode};
+use serde::Serialize;
+use tauri::{AppHandle, Runtime};
+use tokio::sync::Mutex;
+
+use crate::{
+    commands::listen_backend_events,
+    error::{AppError, ErrorCode},
+    network::Network,
+    AppState,
+};
+
+/// Lifecycle of the node, emitted to the UI on the "NodeState" event on every change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum NodeState {
+    Uninitialized,
+    Starting,
+    Running,
+    Stopping,
+    Stopped,
+}
+
+impl NodeState {
+    /// A node can be started on startup and again once the previous one has stopped.
+    fn check_can_start(self) -> Result<(), AppError> {
+        match self {
+            NodeState::Uninitialized | NodeState::Stopped => Ok(()),
+            NodeState::Running => Err(AppError::new(
+                ErrorCode::NodeAlreadyInitialized,
+                "Node is already initialized",
+            )),
+            NodeState::Starting | NodeState::Stopping => Err(AppError::node_busy()),
+        }
+    }
+}
+
+/// Starts the node and the task forwarding its events to the UI.
+///
+/// Only allowed when no node is running, i.e. on startup or after `stop_node`.
+pub async fn start_node<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    state: &Mutex<AppState>,
+    network: Network,
+    wallet_mode: WalletMode,
+) -> Result<ChainInfo, AppError> {
+    let net_type = network.to_init_network();
+
+    {
+        let mut app_state = state.lock().await;
+        app_state.node_state.check_can_start()?;
+        app_state.set_node_state(app_handle, NodeState::Starting);
+    }
+
+    let backend_controls =
+        node_gui_backend::node_initialize(TimeGetter::default(), net_type, wallet_mode).await;
+
+    let mut app_state = state.lock().await;
+    let backend_controls = match backend_controls {
+        Ok(backend_controls) => backend_controls,
+        Err(e) => {
+            app_state.set_node_state(app_handle, NodeState::Stopped);
+            return Err(AppError::new(ErrorCode::Backend, e.to_string()));
+        }
+    };
+
+    app_state.backend_sender = Some(backend_controls.backend_sender);
+    app_state.chain_config = Some(backend_controls.initialized_node.chain_config.clone());
+    app_state.network = Some(network);
+
+    let listener_task = tokio::spawn(listen_backend_events(
+        app_handle.clone(),
+        backend_controls.initialized_node.chain_config.clone(),
+        app_state.event_state.clone(),
+        backend_controls.backend_receiver,
+        backend_controls.low_priority_backend_receiver,
+    ));
+    app_state.listener_task = Some(listener_task);
+
+    app_state.set_node_state(app_handle, NodeState::Running);
+
+    Ok(backend_controls.initialized_node.chain_info)
+}
+
+/// Shuts the backend down and waits until it has stopped sending events.
+///
+/// Does nothing if the node is not running.
+pub async fn stop_node<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    state: &Mutex<AppState>,
+) -> Result<(), AppError> {
+    let listener_task = {
+        let mut app_state = state.lock().await;
+        match app_state.node_state {
+            NodeState::Running => {}
+            NodeState::Uninitialized | NodeState::Stopped => return Ok(()),
+            NodeState::Starting | NodeState::Stopping => return Err(AppError::node_busy()),
+        }
+
+        app_state.backend_sender()?.send(BackendRequest::Shutdown);
+
+        // Further commands are rejected as if the node was never started
+        app_state.backend_sender = None;
+        app_state.chain_config = None;
+        app_state.network = None;
+        app_state.set_node_state(app_handle, NodeState::Stopping);
+
+        app_state.listener_task.take()
+    };
+
+    // The listener returns once the backend has dropped its event senders, which
+    // happens only after the node has been shut down.
+    let join_result = match listener_task {
+        Some(listener_task) => listener_task.await,
+        None => Ok(()),
+    };
+
+    let mut app_state = state.lock().await;
+    {
+        let mut event_state = app_state.event_state.lock().await;
+        event_state.request_tracker.drop_pending();
+        event_state.event_cache.clear();
+        event_state.utxos.clear();
+        event_state.histories.clear();
+        event_state.transactions.clear();
+        event_state.wallet_registry.clear_open_wallets();
+    }
+    app_state.set_node_state(app_handle, NodeState::Stopped);
+
+    join_result.map_err(|e| AppError::new(ErrorCode::Backend, e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use tauri::{
+        test::{mock_app, MockRuntime},
+        App, Manager,
+    };
+
+    use super::*;
+
+    fn app_in_state(node_state: NodeState) -> App<MockRuntime> {
+        let app = mock_app();
+        let config_dir = std::env::temp_dir().join("node-gui-x-lifecycle");
+        let mut app_state = AppState::new(&config_dir);
+        app_state.node_state = node_state;
+        app.manage(Mutex::new(app_state));
+        app
+    }
+
+    fn node_state(app: &App<MockRuntime>) -> NodeState {
+        let state = app.state::<Mutex<AppState>>();
+        tauri::async_runtime::block_on(state.lock()).node_state
+    }
+
+    #[test]
+    fn nodes_can_be_restarted_once_stopped() {
+        assert!(NodeState::Uninitialized.check_can_start().is_ok());
+        assert!(NodeState::Stopped.check_can_start().is_ok());
+    }
+
+    #[test]
+    fn running_nodes_cannot_be_initialized_twice() {
+        let app = app_in_state(NodeState::Running);
+        let result = tauri::async_runtime::block_on(start_node(
+            app.handle(),
+            &app.state::<Mutex<AppState>>(),
+            Network::Testnet,
+            WalletMode::Hot,
+        ));
+
+        assert_eq!(
+            result.unwrap_err().code(),
+            ErrorCode::NodeAlreadyInitialized
+        );
+        assert_eq!(node_state(&app), NodeState::Running);
+    }
+
+    #[test]
+    fn transitions_in_progress_are_not_interrupted() {
+        for transition in [NodeState::Starting, NodeState::Stopping] {
+            let app = app_in_state(transition);
+            let state = app.state::<Mutex<AppState>>();
+
+            let started = tauri::async_runtime::block_on(start_node(
+                app.handle(),
+                &state,
+                Network::Testnet,
+                WalletMode::Hot,
+            ));
+            assert_eq!(started.unwrap_err().code(), ErrorCode::NodeBusy);
+
+            let stopped = tauri::async_runtime::block_on(stop_node(app.handle(), &state));
+            assert_eq!(stopped.unwrap_err().code(), ErrorCode::NodeBusy);
+            assert_eq!(node_state(&app), transition);
+        }
+    }
+
+    #[test]
+    fn stopping_a_stopped_node_does_nothing() {
+        for stopped in [NodeState::Uninitialized, NodeState::Stopped] {
+            let app = app_in_state(stopped);
+            let result = tauri::async_runtime::block_on(stop_node(
+                app.handle(),
+                &app.state::<Mutex<AppState>>(),
+            ));
+
+            assert!(result.is_ok());
+            assert_eq!(node_state(&app), stopped);
+        }
+    }
+}
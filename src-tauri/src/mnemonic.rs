@@ -0,0 +1,245 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/node-gui-x/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{path::Path, sync::Arc};
+
+use common::{
+    chain::{ChainConfig, GenBlock},
+    primitives::{BlockHeight, Id},
+};
+use serde::Serialize;
+use wallet_controller::{
+    mnemonic::{generate_new_mnemonic, Language, Mnemonic},
+    HotController,
+};
+use wallet_types::{seed_phrase::StoreSeedPhrase, wallet_type::WalletType};
+
+use crate::error::{AppError, ErrorCode};
+
+const VALID_WORD_COUNTS: [usize; 5] = [12, 15, 18, 21, 24];
+
+/// Words in the BIP39 english list are unique by their first four letters.
+const SUGGESTION_PREFIX_LEN: usize = 4;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InvalidWord {
+    index: usize,
+    word: String,
+    suggestions: Vec<&'static str>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MnemonicValidation {
+    valid: bool,
+    word_count: usize,
+    word_count_valid: bool,
+    invalid_words: Vec<InvalidWord>,
+    /// `None` when the checksum could not be checked because of other errors
+    checksum_valid: Option<bool>,
+    error: Option<String>,
+}
+
+pub fn generate() -> String {
+    generate_new_mnemonic(Language::English).to_string()
+}
+
+/// Checks a recovery phrase word by word, so the UI can point at the exact mistake.
+pub fn validate(phrase: &str) -> MnemonicValidation {
+    let words = phrase.split_whitespace().map(str::to_lowercase).collect::<Vec<_>>();
+    let word_count_valid = VALID_WORD_COUNTS.contains(&words.len());
+
+    // The wallet's `Language` is the bip39 one, with its word list
+    let word_list = Language::English;
+    let invalid_words = words
+        .iter()
+        .enumerate()
+        .filter(|(_, word)| word_list.find_word(word).is_none())
+        .map(|(index, word)| {
+            let prefix = word.chars().take(SUGGESTION_PREFIX_LEN).collect::<String>();
+            InvalidWord {
+                index,
+                word: word.clone(),
+                suggestions: word_list.words_by_prefix(&prefix).to_vec(),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let (checksum_valid, error) = if !word_count_valid {
+        (
+            None,
+            Some(format!(
+                "Invalid number of words: {}, expected one of {:?}",
+                words.len(),
+                VALID_WORD_COUNTS
+            )),
+        )
+    } else if let Some(first_invalid) = invalid_words.first() {
+        (
+            None,
+            Some(format!(
+                "Unknown word \"{}\" at position {}",
+                first_invalid.word,
+                first_invalid.index + 1
+            )),
+        )
+    } else {
+        match Mnemonic::parse(words.join(" ")) {
+            Ok(_) => (Some(true), None),
+            Err(_) => (Some(false), Some("Invalid mnemonic checksum".to_owned())),
+        }
+    };
+
+    MnemonicValidation {
+        valid: error.is_none(),
+        word_count: words.len(),
+        word_count_valid,
+        invalid_words,
+        checksum_valid,
+        error,
+    }
+}
+
+/// Writes the wallet file of a mnemonic protected by a passphrase, which
+/// `BackendRequest::RecoverWallet` cannot carry, for the backend to open afterwards.
+///
+/// With `best_block` the wallet starts from that block, as for a freshly generated
+/// mnemonic, otherwise it scans the chain from the genesis.
+pub fn create_wallet_file(
+    chain_config: Arc<ChainConfig>,
+    file_path: &Path,
+    mnemonic: Mnemonic,
+    passphrase: &str,
+    wallet_type: WalletType,
+    best_block: Option<(BlockHeight, Id<GenBlock>)>,
+) -> Result<(), AppError> {
+    let wallet = match best_block {
+        Some(best_block) => HotController::create_wallet(
+            chain_config,
+            file_path,
+            mnemonic,
+            Some(passphrase),
+            StoreSeedPhrase::Store,
+            best_block,
+            wallet_type,
+        ),
+        None => HotController::recover_wallet(
+            chain_config,
+            file_path,
+            mnemonic,
+            Some(passphrase),
+            StoreSeedPhrase::Store,
+            wallet_type,
+        ),
+    };
+    // Dropping the wallet closes its file, for the backend to open it
+    wallet.map(drop).map_err(|e| AppError::new(ErrorCode::Wallet, e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use common::chain::config::create_unit_test_config;
+    use wallet::key_chain::MasterKeyChain;
+
+    use super::*;
+
+    const VALID_PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon \
+        abandon abandon abandon abandon about";
+
+    #[test]
+    fn generated_and_known_phrases_are_valid() {
+        for phrase in [generate(), VALID_PHRASE.to_uppercase()] {
+            let validation = validate(&phrase);
+            assert!(validation.valid, "{:?}", validation.error);
+            assert_eq!(validation.checksum_valid, Some(true));
+            assert!(validation.invalid_words.is_empty());
+        }
+    }
+
+    #[test]
+    fn bad_checksum_is_reported() {
+        let phrase = VALID_PHRASE.replace("about", "abandon");
+        let validation = validate(&phrase);
+
+        assert!(!validation.valid);
+        assert!(validation.word_count_valid);
+        assert!(validation.invalid_words.is_empty());
+        assert_eq!(validation.checksum_valid, Some(false));
+    }
+
+    #[test]
+    fn unknown_words_come_with_suggestions() {
+        let phrase = VALID_PHRASE.replace("about", "aboutt");
+        let validation = validate(&phrase);
+
+        assert!(!validation.valid);
+        assert_eq!(validation.checksum_valid, None);
+        assert_eq!(validation.invalid_words.len(), 1);
+        let invalid = &validation.invalid_words[0];
+        assert_eq!(invalid.index, 11);
+        assert_eq!(invalid.word, "aboutt");
+        assert_eq!(invalid.suggestions, ["about"]);
+        assert!(validation.error.unwrap().contains("position 12"));
+
+        let validation = validate(&VALID_PHRASE.replace("about", "qqqq"));
+        assert!(validation.invalid_words[0].suggestions.is_empty());
+    }
+
+    #[test]
+    fn wrong_word_count_is_reported_first() {
+        let phrase = VALID_PHRASE.replace(" about", "");
+        let validation = validate(&phrase);
+
+        assert!(!validation.valid);
+        assert_eq!(validation.word_count, 11);
+        assert!(!validation.word_count_valid);
+        assert_eq!(validation.checksum_valid, None);
+        assert!(validation.error.unwrap().starts_with("Invalid number of words"));
+    }
+
+    #[test]
+    fn passphrase_changes_the_wallet_keys() {
+        let phrase = generate();
+        let root_key =
+            |passphrase| MasterKeyChain::mnemonic_to_root_key(&phrase, passphrase).unwrap();
+
+        assert_eq!(root_key(None), root_key(None));
+        assert_ne!(root_key(None), root_key(Some("25th word")));
+        assert_ne!(root_key(Some("25th word")), root_key(Some("26th word")));
+    }
+
+    #[test]
+    fn wallet_files_are_created_with_the_passphrase() {
+        let dir = std::env::temp_dir().join(format!("node-gui-x-mnemonic-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let chain_config = Arc::new(create_unit_test_config());
+        let best_block = (BlockHeight::zero(), chain_config.genesis_block_id());
+
+        for (name, best_block) in [("created.dat", Some(best_block)), ("recovered.dat", None)] {
+            let file_path = dir.join(name);
+            create_wallet_file(
+                chain_config.clone(),
+                &file_path,
+                Mnemonic::parse(generate()).unwrap(),
+                "25th word",
+                WalletType::Hot,
+                best_block,
+            )
+            .unwrap();
+            assert!(file_path.exists());
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,47 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/node-gui-x/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use node_gui_backend::InitNetwork;
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+
+/// The network the node was started on, as selected in the UI.
+///
+/// Only the public networks can be started: `node_gui_backend::node_initialize` takes an
+/// `InitNetwork`, which has no regtest variant and no way to carry a custom `ChainConfig`
+/// at the pinned revision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Network {
+    Mainnet,
+    Testnet,
+}
+
+impl Network {
+    pub fn from_name(name: &str) -> Result<Self, AppError> {
+        match name {
+            "Mainnet" => Ok(Network::Mainnet),
+            "Testnet" => Ok(Network::Testnet),
+            _ => Err(AppError::invalid_input("Invalid network selection")),
+        }
+    }
+
+    pub fn to_init_network(self) -> InitNetwork {
+        match self {
+            Network::Mainnet => InitNetwork::Mainnet,
+            Network::Testnet => InitNetwork::Testnet,
+        }
+    }
+}
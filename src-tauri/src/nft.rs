@@ -0,0 +1,112 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/node-gui-x/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{console, error::AppError};
+
+/// Parameters of a new NFT.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NftIssuance {
+    pub name: String,
+    pub ticker: String,
+    pub description: String,
+    /// Hex encoded hash of the media
+    pub media_hash: String,
+    pub destination_address: String,
+    #[serde(default)]
+    pub media_uri: Option<String>,
+    #[serde(default)]
+    pub icon_uri: Option<String>,
+    #[serde(default)]
+    pub additional_metadata_uri: Option<String>,
+}
+
+impl NftIssuance {
+    pub fn command_line(&self) -> Result<String, AppError> {
+        for (field, value) in [
+            ("name", &self.name),
+            ("ticker", &self.ticker),
+            ("description", &self.description),
+            ("media hash", &self.media_hash),
+        ] {
+            if value.is_empty() {
+                return Err(AppError::invalid_input(format!(
+                    "The {field} cannot be empty"
+                )));
+            }
+        }
+        if !self.media_hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(AppError::invalid_input(
+                "The media hash must be hex encoded",
+            ));
+        }
+
+        let mut args = vec![
+            self.ticker.as_str(),
+            self.destination_address.as_str(),
+            self.media_hash.as_str(),
+            self.name.as_str(),
+            self.description.as_str(),
+        ];
+        for (flag, value) in [
+            ("--media-uri", &self.media_uri),
+            ("--icon-uri", &self.icon_uri),
+            ("--additional-metadata-uri", &self.additional_metadata_uri),
+        ] {
+            if let Some(value) = value {
+                args.extend([flag, value.as_str()]);
+            }
+        }
+
+        Ok(console::command_line("nft-issue-new", &args))
+    }
+}
+
+/// An NFT held by an account, with its metadata.
+#[derive(Debug, Clone, Serialize)]
+pub struct NftInfo {
+    token_id: String,
+    name: String,
+    ticker: String,
+    description: String,
+    media_hash: String,
+    media_uri: Option<String>,
+    icon_uri: Option<String>,
+    additional_metadata_uri: Option<String>,
+    creator: Option<String>,
+}
+
+impl NftInfo {
+    /// Reads the token info printed by the `token-info` console command, `None` if the
+    /// token is not an NFT.
+    pub fn from_json(token_id: &str, info: &Value) -> Option<Self> {
+        let metadata = info.get("NonFungibleToken")?.get("metadata")?;
+        let text = |field: &str| metadata.get(field).and_then(console::text).map(str::to_owned);
+
+        Some(NftInfo {
+            token_id: token_id.to_owned(),
+            name: text("name").unwrap_or_default(),
+            ticker: text("ticker").unwrap_or_default(),
+            description: text("description").unwrap_or_default(),
+            media_hash: text("media_hash").unwrap_or_default(),
+            media_uri: text("media_uri"),
+            icon_uri: text("icon_uri"),
+            additional_metadata_uri: text("additional_metadata_uri"),
+            creator: text("creator"),
+        })
+    }
+}
@@ -0,0 +1,318 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/node-gui-x/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common::{
+    address::Address,
+    chain::{ChainConfig, Destination},
+    primitives::Amount,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, ErrorCode};
+
+/// One recipient of a batch payment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentOutput {
+    pub address: String,
+    pub amount: String,
+    /// Token paid instead of coins. Batch transactions are composed from coin outputs
+    /// only, so an output with a token is rejected; tokens go through `send_tokens`.
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+/// A row of a payout CSV file: address, amount and optionally a label and a token.
+#[derive(Debug, Clone, Serialize)]
+pub struct PayoutRow {
+    line: u64,
+    label: Option<String>,
+    output: PaymentOutput,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RowError {
+    line: u64,
+    message: String,
+}
+
+/// Outputs of the transaction a payout file would produce, shown before building it.
+#[derive(Debug, Clone, Serialize)]
+pub struct PayoutPreview {
+    valid: bool,
+    rows: Vec<PayoutRow>,
+    errors: Vec<RowError>,
+    /// `None` unless every row is valid
+    total: Option<Amount>,
+}
+
+/// Checks a single output against the chain and returns its amount.
+pub fn validate_output(
+    chain_config: &ChainConfig,
+    output: &PaymentOutput,
+) -> Result<Amount, String> {
+    if let Some(token) = &output.token {
+        return Err(format!(
+            "Token {token} cannot be paid in a batch, only coins can"
+        ));
+    }
+
+    Address::<Destination>::from_string(chain_config, &output.address)
+        .map_err(|e| format!("Invalid address: {e}"))?;
+
+    let amount = Amount::from_fixedpoint_str(&output.amount, chain_config.coin_decimals())
+        .ok_or_else(|| format!("Invalid amount: {}", output.amount))?;
+    if amount == Amount::ZERO {
+        return Err("Amount must be greater than zero".to_owned());
+    }
+
+    Ok(amount)
+}
+
+/// Checks all outputs up front and returns their addresses and amounts, or every
+/// problem found.
+pub fn validate_outputs(
+    chain_config: &ChainConfig,
+    outputs: &[PaymentOutput],
+) -> Result<Vec<(String, Amount)>, AppError> {
+    if outputs.is_empty() {
+        return Err(AppError::invalid_input("No outputs given"));
+    }
+
+    let mut total = Some(Amount::ZERO);
+    let mut amounts = Vec::new();
+    let mut errors = Vec::new();
+    for (index, output) in outputs.iter().enumerate() {
+        match validate_output(chain_config, output) {
+            Ok(amount) => {
+                total = total.and_then(|total| total + amount);
+                amounts.push((output.address.clone(), amount));
+            }
+            Err(e) => errors.push(format!("Output {}: {e}", index + 1)),
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(AppError::invalid_input(errors.join("\n")));
+    }
+    if total.is_none() {
+        return Err(AppError::new(
+            ErrorCode::InvalidAmount,
+            "Total amount overflows",
+        ));
+    }
+    Ok(amounts)
+}
+
+/// Parses a payout CSV file, checking every row so all mistakes can be fixed at once.
+///
+/// The columns are address, amount, label and token, the last two optional. A first row
+/// starting with "address" is taken as a header and skipped.
+pub fn parse_csv(chain_config: &ChainConfig, data: &[u8]) -> PayoutPreview {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(data);
+
+    let mut rows = Vec::new();
+    let mut errors = Vec::new();
+    let mut total = Some(Amount::ZERO);
+    for (index, record) in reader.records().enumerate() {
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                let line = e.position().map_or(index as u64 + 1, csv::Position::line);
+                errors.push(RowError {
+                    line,
+                    message: e.to_string(),
+                });
+                continue;
+            }
+        };
+        let line = record.position().map_or(index as u64 + 1, csv::Position::line);
+
+        let field = |i: usize| record.get(i).filter(|field| !field.is_empty());
+        if index == 0 && field(0).is_some_and(|field| field.eq_ignore_ascii_case("address")) {
+            continue;
+        }
+        if record.iter().all(str::is_empty) {
+            continue;
+        }
+
+        let (Some(address), Some(amount)) = (field(0), field(1)) else {
+            errors.push(RowError {
+                line,
+                message: "Expected at least an address and an amount".to_owned(),
+            });
+            continue;
+        };
+        let output = PaymentOutput {
+            address: address.to_owned(),
+            amount: amount.to_owned(),
+            token: field(3).map(str::to_owned),
+        };
+
+        match validate_output(chain_config, &output) {
+            Ok(amount) => total = total.and_then(|total| total + amount),
+            Err(message) => errors.push(RowError { line, message }),
+        }
+        rows.push(PayoutRow {
+            line,
+            label: field(2).map(str::to_owned),
+            output,
+        });
+    }
+
+    if total.is_none() {
+        errors.push(RowError {
+            line: 1,
+            message: "Total amount overflows".to_owned(),
+        });
+    }
+    if rows.is_empty() && errors.is_empty() {
+        errors.push(RowError {
+            line: 1,
+            message: "The file contains no payouts".to_owned(),
+        });
+    }
+
+    let valid = errors.is_empty();
+    PayoutPreview {
+        valid,
+        rows,
+        errors,
+        total: total.filter(|_| valid),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{
+        address::pubkeyhash::PublicKeyHash,
+        chain::config::{create_testnet, create_unit_test_config},
+    };
+
+    use super::*;
+
+    fn address(chain_config: &ChainConfig, byte: u8) -> String {
+        let destination = Destination::PublicKeyHash(PublicKeyHash::repeat_byte(byte));
+        Address::new(chain_config, destination).unwrap().to_string()
+    }
+
+    fn lines(errors: &[RowError]) -> Vec<u64> {
+        errors.iter().map(|error| error.line).collect()
+    }
+
+    #[test]
+    fn header_and_empty_rows_are_skipped() {
+        let chain_config = create_unit_test_config();
+        let csv = format!(
+            "Address,Amount,Label\n{},1.5,alice\n\n , \n{},2\n",
+            address(&chain_config, 1),
+            address(&chain_config, 2)
+        );
+
+        let preview = parse_csv(&chain_config, csv.as_bytes());
+        assert!(preview.valid, "{:?}", preview.errors);
+        assert_eq!(preview.rows.len(), 2);
+        assert_eq!(preview.rows[0].line, 2);
+        assert_eq!(preview.rows[0].label.as_deref(), Some("alice"));
+        assert_eq!(preview.rows[1].line, 5);
+        assert_eq!(preview.rows[1].label, None);
+        assert_eq!(
+            preview.total,
+            Amount::from_fixedpoint_str("3.5", chain_config.coin_decimals())
+        );
+    }
+
+    #[test]
+    fn every_bad_row_is_reported_with_its_line() {
+        let chain_config = create_unit_test_config();
+        let too_precise = format!(
+            "1.{}",
+            "1".repeat(chain_config.coin_decimals() as usize + 1)
+        );
+        let csv = format!(
+            "{},1\n{},1\n{},{too_precise}\n{}\n{},0\n",
+            address(&chain_config, 1),
+            address(&create_testnet(), 2),
+            address(&chain_config, 3),
+            address(&chain_config, 4),
+            address(&chain_config, 5),
+        );
+
+        let preview = parse_csv(&chain_config, csv.as_bytes());
+        assert!(!preview.valid);
+        assert_eq!(preview.total, None);
+        assert_eq!(lines(&preview.errors), [2, 3, 4, 5]);
+        assert!(preview.errors[0].message.starts_with("Invalid address"));
+        assert!(preview.errors[1].message.starts_with("Invalid amount"));
+    }
+
+    #[test]
+    fn token_rows_are_rejected() {
+        let chain_config = create_unit_test_config();
+        let csv = format!("{},1,,tmltk1token\n", address(&chain_config, 1));
+
+        let preview = parse_csv(&chain_config, csv.as_bytes());
+        assert_eq!(lines(&preview.errors), [1]);
+        assert_eq!(preview.rows[0].output.token.as_deref(), Some("tmltk1token"));
+    }
+
+    #[test]
+    fn files_without_payouts_are_invalid() {
+        let chain_config = create_unit_test_config();
+        let preview = parse_csv(&chain_config, b"address,amount\n\n");
+        assert!(!preview.valid);
+        assert_eq!(lines(&preview.errors), [1]);
+    }
+
+    #[test]
+    fn outputs_are_validated_before_anything_is_built() {
+        let chain_config = create_unit_test_config();
+        let output = |address, amount: &str| PaymentOutput {
+            address,
+            amount: amount.to_owned(),
+            token: None,
+        };
+        let token_output = PaymentOutput {
+            token: Some("tmltk1token".to_owned()),
+            ..output(address(&chain_config, 3), "1")
+        };
+        let outputs = [
+            output(address(&chain_config, 1), "1"),
+            output("not an address".to_owned(), "1"),
+            output(address(&chain_config, 2), "-1"),
+            token_output,
+        ];
+
+        let error = validate_outputs(&chain_config, &outputs).unwrap_err();
+        assert_eq!(error.code(), ErrorCode::InvalidInput);
+        assert!(error.to_string().contains("Output 2: Invalid address"));
+        assert!(error.to_string().contains("Output 3: Invalid amount"));
+        assert!(error.to_string().contains("Output 4: Token tmltk1token"));
+        assert!(!error.to_string().contains("Output 1"));
+
+        let outputs = [output(address(&chain_config, 1), "1.25")];
+        let amounts = validate_outputs(&chain_config, &outputs).unwrap();
+        assert_eq!(
+            amounts,
+            [(
+                outputs[0].address.clone(),
+                Amount::from_fixedpoint_str("1.25", chain_config.coin_decimals()).unwrap()
+            )]
+        );
+    }
+}
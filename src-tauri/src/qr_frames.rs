@@ -0,0 +1,265 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/node-gui-x/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::error::AppError;
+
+const FRAME_PREFIX: &str = "MLTX1";
+
+/// Hex characters per frame, few enough for a QR code phone cameras read reliably
+const DEFAULT_CHUNK_LEN: usize = 400;
+
+/// Smaller frames would take too long to scan
+const MIN_CHUNK_LEN: usize = 100;
+
+/// Largest transaction the mempool accepts, in bytes
+const MAX_TRANSACTION_SIZE: usize = 1_000_000;
+
+/// Frames of the largest transaction cut into the smallest chunks, so that a crafted frame
+/// cannot announce an arbitrary number of them
+const MAX_FRAME_COUNT: usize = (2 * MAX_TRANSACTION_SIZE).div_ceil(MIN_CHUNK_LEN);
+
+/// Splits a hex encoded transaction into QR frames to be shown one after the other.
+///
+/// Each frame reads `MLTX1:<index>/<count>:<transaction crc>:<chunk crc>:<chunk>`, so
+/// the frames can be scanned in any order and corrupted ones are detected.
+pub fn encode(transaction: &str, chunk_len: Option<usize>) -> Result<Vec<String>, AppError> {
+    let transaction = transaction.trim().to_ascii_lowercase();
+    if transaction.is_empty() || hex::decode(&transaction).is_err() {
+        return Err(AppError::invalid_input(
+            "The transaction must be hex encoded",
+        ));
+    }
+    if transaction.len() > 2 * MAX_TRANSACTION_SIZE {
+        return Err(AppError::invalid_input("The transaction is too large"));
+    }
+    let chunk_len = chunk_len.unwrap_or(DEFAULT_CHUNK_LEN);
+    if chunk_len < MIN_CHUNK_LEN {
+        return Err(AppError::invalid_input(format!(
+            "The frame size must be at least {MIN_CHUNK_LEN}"
+        )));
+    }
+
+    let transaction_crc = crc32fast::hash(transaction.as_bytes());
+    // Hex is ASCII, so the chunks always end on a character boundary
+    let chunks = transaction.as_bytes().chunks(chunk_len).collect::<Vec<_>>();
+    let frames = chunks
+        .iter()
+        .enumerate()
+        .map(|(index, chunk)| {
+            format!(
+                "{FRAME_PREFIX}:{}/{}:{transaction_crc:08x}:{:08x}:{}",
+                index + 1,
+                chunks.len(),
+                crc32fast::hash(chunk),
+                String::from_utf8_lossy(chunk),
+            )
+        })
+        .collect();
+
+    Ok(frames)
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RejectedFrame {
+    frame: String,
+    reason: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct QrDecodeResult {
+    /// Number of frames of the transaction, known once a valid frame has been scanned
+    frame_count: Option<usize>,
+    /// Indexes, starting from 1, of the frames still to be scanned
+    missing: Vec<usize>,
+    rejected: Vec<RejectedFrame>,
+    /// The reassembled hex encoded transaction, once all frames have been scanned
+    transaction: Option<String>,
+    error: Option<String>,
+}
+
+struct Frame<'a> {
+    index: usize,
+    count: usize,
+    transaction_crc: u32,
+    chunk: &'a str,
+}
+
+/// Reassembles a transaction from the frames scanned so far, which may come in any
+/// order and with duplicates, telling which frames are still missing.
+///
+/// The first valid frame decides which transaction is being scanned, frames of any
+/// other transaction are rejected.
+pub fn decode(scanned: &[String]) -> QrDecodeResult {
+    let mut chunks = BTreeMap::new();
+    let mut expected = None;
+    let mut rejected = Vec::new();
+
+    for text in scanned {
+        let frame = match parse_frame(text) {
+            Ok(frame) => frame,
+            Err(reason) => {
+                rejected.push(RejectedFrame {
+                    frame: text.clone(),
+                    reason,
+                });
+                continue;
+            }
+        };
+
+        let transaction_key = (frame.count, frame.transaction_crc);
+        if *expected.get_or_insert(transaction_key) != transaction_key {
+            rejected.push(RejectedFrame {
+                frame: text.clone(),
+                reason: "Frame of another transaction".to_owned(),
+            });
+            continue;
+        }
+        chunks.insert(frame.index, frame.chunk);
+    }
+
+    let Some((count, transaction_crc)) = expected else {
+        return QrDecodeResult {
+            frame_count: None,
+            missing: Vec::new(),
+            rejected,
+            transaction: None,
+            error: None,
+        };
+    };
+
+    let missing = (1..=count).filter(|index| !chunks.contains_key(index)).collect::<Vec<_>>();
+    let (transaction, error) = if missing.is_empty() {
+        let transaction = chunks.into_values().collect::<String>();
+        if crc32fast::hash(transaction.as_bytes()) == transaction_crc {
+            (Some(transaction), None)
+        } else {
+            (
+                None,
+                Some("The reassembled transaction is corrupted".to_owned()),
+            )
+        }
+    } else {
+        (None, None)
+    };
+
+    QrDecodeResult {
+        frame_count: Some(count),
+        missing,
+        rejected,
+        transaction,
+        error,
+    }
+}
+
+fn parse_frame(text: &str) -> Result<Frame<'_>, String> {
+    let parts = text.trim().splitn(5, ':').collect::<Vec<_>>();
+    let [FRAME_PREFIX, position, transaction_crc, chunk_crc, chunk] = parts[..] else {
+        return Err("Not a transaction frame".to_owned());
+    };
+
+    let (index, count) = position
+        .split_once('/')
+        .and_then(|(index, count)| Some((index.parse().ok()?, count.parse().ok()?)))
+        .filter(|(index, count)| *count <= MAX_FRAME_COUNT && (1..=*count).contains(index))
+        .ok_or_else(|| format!("Invalid frame position {position}"))?;
+    let transaction_crc = u32::from_str_radix(transaction_crc, 16)
+        .map_err(|_| format!("Invalid checksum {transaction_crc}"))?;
+    let chunk_crc =
+        u32::from_str_radix(chunk_crc, 16).map_err(|_| format!("Invalid checksum {chunk_crc}"))?;
+    if crc32fast::hash(chunk.as_bytes()) != chunk_crc {
+        return Err(format!("Frame {index} is corrupted"));
+    }
+
+    Ok(Frame {
+        index,
+        count,
+        transaction_crc,
+        chunk,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transaction(len: usize) -> String {
+        (0..len).map(|i| format!("{:02x}", i % 256)).collect()
+    }
+
+    #[test]
+    fn frames_reassemble_in_any_order() {
+        let transaction = transaction(500);
+        let mut frames = encode(&transaction, Some(300)).unwrap();
+        assert_eq!(frames.len(), 4);
+
+        frames.reverse();
+        frames.push(frames[1].clone());
+        let result = decode(&frames);
+        assert_eq!(result.frame_count, Some(4));
+        assert!(result.missing.is_empty());
+        assert!(result.rejected.is_empty());
+        assert_eq!(result.transaction, Some(transaction));
+    }
+
+    #[test]
+    fn missing_frames_are_listed() {
+        let frames = encode(&transaction(500), Some(300)).unwrap();
+
+        let result = decode(&[frames[3].clone(), frames[0].clone()]);
+        assert_eq!(result.missing, [2, 3]);
+        assert_eq!(result.transaction, None);
+    }
+
+    #[test]
+    fn corrupted_frames_are_rejected() {
+        let frames = encode(&transaction(500), Some(300)).unwrap();
+        let corrupted = frames[0].replace(":0001", ":0002");
+        assert_ne!(corrupted, frames[0]);
+
+        let result = decode(&[corrupted, "hello".to_owned()]);
+        assert_eq!(result.frame_count, None);
+        assert_eq!(result.rejected.len(), 2);
+    }
+
+    #[test]
+    fn frames_of_another_transaction_are_rejected() {
+        let frames = encode(&transaction(500), Some(300)).unwrap();
+        let foreign = encode(&transaction(400), Some(300)).unwrap();
+
+        let result = decode(&[frames[0].clone(), foreign[1].clone()]);
+        assert_eq!(result.frame_count, Some(4));
+        assert_eq!(result.rejected.len(), 1);
+        assert_eq!(result.missing, [2, 3, 4]);
+    }
+
+    #[test]
+    fn frame_count_is_bounded() {
+        let chunk = "00";
+        let frame = format!(
+            "{FRAME_PREFIX}:1/{}:{:08x}:{:08x}:{chunk}",
+            usize::MAX,
+            crc32fast::hash(chunk.as_bytes()),
+            crc32fast::hash(chunk.as_bytes()),
+        );
+
+        let result = decode(&[frame]);
+        assert_eq!(result.frame_count, None);
+        assert_eq!(result.rejected.len(), 1);
+    }
+}
@@ -13,7 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.#![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use common::chain::DelegationId;
+use common::chain::{DelegationId, UtxoOutPoint};
 use node_gui_backend::{
     messages::{TransactionInfo, WalletId},
     AccountId,
@@ -21,9 +21,19 @@ use node_gui_backend::{
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
+use crate::{
+    fee::FeeRate,
+    nft::NftIssuance,
+    payout::PaymentOutput,
+    token::{TokenAction, TokenIssuance},
+};
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct OpenCreateWalletRequest {
     pub mnemonic: String,
+    /// Optional BIP39 passphrase ("25th word"), none when empty
+    #[serde(default)]
+    pub passphrase: Option<String>,
     pub file_path: String,
     pub import: bool,
     pub wallet_type: String,
@@ -41,6 +51,76 @@ pub struct SendAmountRequest {
     pub account_id: AccountId,
     pub amount: String,
     pub address: String,
+    /// Outputs to spend, as listed by `list_utxos`; left to the wallet when empty
+    #[serde(default)]
+    pub inputs: Vec<UtxoOutPoint>,
+    #[serde(default)]
+    pub fee_rate: FeeRate,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SendTokenRequest {
+    pub wallet_id: WalletId,
+    pub account_id: AccountId,
+    pub token_id: String,
+    pub address: String,
+    pub amount: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IssueTokenRequest {
+    pub wallet_id: WalletId,
+    pub account_id: AccountId,
+    pub issuance: TokenIssuance,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ManageTokenRequest {
+    pub wallet_id: WalletId,
+    pub account_id: AccountId,
+    pub token_id: String,
+    pub action: TokenAction,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IssueNftRequest {
+    pub wallet_id: WalletId,
+    pub account_id: AccountId,
+    pub issuance: NftIssuance,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransferNftRequest {
+    pub wallet_id: WalletId,
+    pub account_id: AccountId,
+    pub token_id: String,
+    pub address: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SweepRequest {
+    pub wallet_id: WalletId,
+    pub account_id: AccountId,
+    pub address: String,
+    /// Outpoints of the outputs to spend, as listed by `list_utxos`; all spendable
+    /// outputs when empty
+    #[serde(default)]
+    pub inputs: Vec<UtxoOutPoint>,
+    #[serde(default)]
+    pub fee_rate: FeeRate,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchSendRequest {
+    pub wallet_id: WalletId,
+    pub account_id: AccountId,
+    pub outputs: Vec<PaymentOutput>,
+    /// Outpoints of the outputs to spend, as listed by `list_utxos`; picked from the
+    /// spendable outputs of the account when empty
+    #[serde(default)]
+    pub inputs: Vec<UtxoOutPoint>,
+    #[serde(default)]
+    pub fee_rate: FeeRate,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -51,6 +131,10 @@ pub struct StakeAmountRequest {
     pub mpt: String,
     pub cost_per_block: String,
     pub decommission_address: String,
+    /// Outputs to spend, left to the wallet when empty; the wallet cannot stake from
+    /// chosen outputs yet, so a non-empty list is rejected
+    #[serde(default)]
+    pub inputs: Vec<UtxoOutPoint>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -73,6 +157,10 @@ pub struct StakingDelegateRequest {
     pub account_id: AccountId,
     pub delegation_id: DelegationId,
     pub delegation_amount: String,
+    /// Outputs to spend, left to the wallet when empty; the wallet cannot delegate from
+    /// chosen outputs yet, so a non-empty list is rejected
+    #[serde(default)]
+    pub inputs: Vec<UtxoOutPoint>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -0,0 +1,253 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/node-gui-x/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use node_gui_backend::{messages::WalletId, AccountId};
+use serde_json::Value;
+use tokio::sync::oneshot;
+
+use crate::error::AppError;
+
+pub type RequestId = u64;
+
+/// Receives the response of a request awaited by a command instead of emitted to the UI.
+pub type ResponseReceiver = oneshot::Receiver<Result<Value, AppError>>;
+
+/// A request sent to the backend that has not been answered yet.
+#[derive(Debug)]
+pub struct PendingRequest {
+    pub request_id: RequestId,
+    pub wallet_id: Option<WalletId>,
+    pub account_id: Option<AccountId>,
+    /// Set when the command that sent the request waits for the response itself
+    pub responder: Option<oneshot::Sender<Result<Value, AppError>>>,
+}
+
+/// Pairs backend events with the commands that caused them.
+///
+/// The backend handles requests one by one in the order they were sent, so a FIFO of
+/// pending request ids per response event is enough to match them up.
+#[derive(Debug, Default)]
+pub struct RequestTracker {
+    next_id: RequestId,
+    pending: BTreeMap<&'static str, VecDeque<PendingRequest>>,
+}
+
+impl RequestTracker {
+    /// Allocates a new request id that will be echoed in the next `event_name` event.
+    ///
+    /// The wallet and account ids are kept so that errors can be attributed to them.
+    pub fn register(
+        &mut self,
+        event_name: &'static str,
+        wallet_id: Option<WalletId>,
+        account_id: Option<AccountId>,
+    ) -> RequestId {
+        self.push(event_name, wallet_id, account_id, None)
+    }
+
+    /// Like `register`, but the next `event_name` event is delivered through the returned
+    /// receiver, serialized, instead of being emitted.
+    pub fn register_awaited(
+        &mut self,
+        event_name: &'static str,
+        wallet_id: Option<WalletId>,
+        account_id: Option<AccountId>,
+    ) -> ResponseReceiver {
+        let (sender, receiver) = oneshot::channel();
+        self.push(event_name, wallet_id, account_id, Some(sender));
+        receiver
+    }
+
+    /// Allocates a request id for a response emitted by the app itself, which no backend
+    /// event answers.
+    pub fn allocate(&mut self) -> RequestId {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    /// Returns the oldest request still waiting for `event_name`, if any.
+    pub fn resolve(&mut self, event_name: &str) -> Option<PendingRequest> {
+        self.pending.get_mut(event_name).and_then(VecDeque::pop_front)
+    }
+
+    /// Like `resolve`, for events which are also sent unrequested: returns the oldest
+    /// request for the given wallet and account, if any.
+    pub fn resolve_for(
+        &mut self,
+        event_name: &str,
+        wallet_id: WalletId,
+        account_id: AccountId,
+    ) -> Option<PendingRequest> {
+        let pending = self.pending.get_mut(event_name)?;
+        let index = pending.iter().position(|request| {
+            request.wallet_id == Some(wallet_id) && request.account_id == Some(account_id)
+        })?;
+        pending.remove(index)
+    }
+
+    /// Like `resolve`, for an event about `wallet_id` and possibly `account_id`: returns
+    /// the oldest request not made for another wallet or account, if any. An event no
+    /// request matches was sent unrequested and leaves the pending requests alone.
+    pub fn resolve_matching(
+        &mut self,
+        event_name: &str,
+        wallet_id: WalletId,
+        account_id: Option<AccountId>,
+    ) -> Option<PendingRequest> {
+        let pending = self.pending.get_mut(event_name)?;
+        let index = pending.iter().position(|request| {
+            same_or_unknown(request.wallet_id, Some(wallet_id))
+                && same_or_unknown(request.account_id, account_id)
+        })?;
+        pending.remove(index)
+    }
+
+    fn push(
+        &mut self,
+        event_name: &'static str,
+        wallet_id: Option<WalletId>,
+        account_id: Option<AccountId>,
+        responder: Option<oneshot::Sender<Result<Value, AppError>>>,
+    ) -> RequestId {
+        let request_id = self.allocate();
+        self.pending.entry(event_name).or_default().push_back(PendingRequest {
+            request_id,
+            wallet_id,
+            account_id,
+            responder,
+        });
+        request_id
+    }
+
+    /// Forgets all pending requests, used when the backend they were sent to is gone.
+    ///
+    /// Commands awaiting a response see their receiver closed.
+    pub fn drop_pending(&mut self) {
+        self.pending.clear();
+    }
+}
+
+fn same_or_unknown<T: PartialEq>(requested: Option<T>, event: Option<T>) -> bool {
+    match (requested, event) {
+        (Some(requested), Some(event)) => requested == event,
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(index: u32) -> AccountId {
+        serde_json::from_value(serde_json::json!(index)).unwrap()
+    }
+
+    fn ids(requests: &[Option<PendingRequest>]) -> Vec<Option<RequestId>> {
+        requests
+            .iter()
+            .map(|request| request.as_ref().map(|request| request.request_id))
+            .collect()
+    }
+
+    #[test]
+    fn requests_are_resolved_in_order_per_event() {
+        let mut tracker = RequestTracker::default();
+        let first = tracker.register("SendAmount", None, None);
+        let other = tracker.register("NewAddress", None, None);
+        let second = tracker.register("SendAmount", None, None);
+        assert_ne!(first, second);
+
+        let resolved = [
+            tracker.resolve("SendAmount"),
+            tracker.resolve("SendAmount"),
+            tracker.resolve("SendAmount"),
+            tracker.resolve("NewAddress"),
+        ];
+        assert_eq!(
+            ids(&resolved),
+            [Some(first), Some(second), None, Some(other)]
+        );
+    }
+
+    #[test]
+    fn abandoned_waiters_keep_their_slot() {
+        let mut tracker = RequestTracker::default();
+        // The command stopped waiting, e.g. timed out, before the backend answered
+        drop(tracker.register_awaited("Broadcast", None, None));
+        let mut waiting = tracker.register_awaited("Broadcast", None, None);
+
+        // The late answer goes to the abandoned request, not to the next one
+        let late = tracker.resolve("Broadcast").unwrap();
+        assert!(late.responder.unwrap().send(Ok(Value::Null)).is_err());
+        assert!(waiting.try_recv().is_err());
+
+        let next = tracker.resolve("Broadcast").unwrap();
+        next.responder.unwrap().send(Ok(Value::Bool(true))).unwrap();
+        assert_eq!(waiting.try_recv().unwrap().unwrap(), Value::Bool(true));
+    }
+
+    #[test]
+    fn dropped_requests_close_their_waiters() {
+        let mut tracker = RequestTracker::default();
+        let mut receiver = tracker.register_awaited("ConsoleResponse", None, None);
+        tracker.register("OpenWallet", None, None);
+
+        tracker.drop_pending();
+        assert!(matches!(
+            receiver.try_recv(),
+            Err(oneshot::error::TryRecvError::Closed)
+        ));
+        assert!(tracker.resolve("OpenWallet").is_none());
+        // Ids are not reused by the next requests
+        assert!(tracker.register("OpenWallet", None, None) > 2);
+    }
+
+    #[test]
+    fn unsolicited_events_do_not_take_a_waiter_slot() {
+        let mut tracker = RequestTracker::default();
+        let wallet_id = WalletId::new();
+        let other_wallet_id = WalletId::new();
+        let account_id = account(0);
+        let request_id = tracker.register("ConsoleResponse", Some(wallet_id), Some(account_id));
+
+        assert!(tracker
+            .resolve_matching("ConsoleResponse", other_wallet_id, Some(account_id))
+            .is_none());
+        assert!(tracker
+            .resolve_matching("ConsoleResponse", wallet_id, Some(account(1)))
+            .is_none());
+        assert!(tracker.resolve_matching("Broadcast", wallet_id, None).is_none());
+
+        let resolved = tracker.resolve_matching("ConsoleResponse", wallet_id, Some(account_id));
+        assert_eq!(ids(&[resolved]), [Some(request_id)]);
+    }
+
+    #[test]
+    fn requests_are_resolved_for_their_account() {
+        let mut tracker = RequestTracker::default();
+        let wallet_id = WalletId::new();
+        let first = tracker.register("TransactionList", Some(wallet_id), Some(account(0)));
+        let second = tracker.register("TransactionList", Some(wallet_id), Some(account(1)));
+
+        let resolved = [
+            tracker.resolve_for("TransactionList", wallet_id, account(1)),
+            tracker.resolve_for("TransactionList", wallet_id, account(1)),
+            tracker.resolve_for("TransactionList", wallet_id, account(0)),
+        ];
+        assert_eq!(ids(&resolved), [Some(second), None, Some(first)]);
+    }
+}
@@ -16,7 +16,7 @@
 use std::collections::BTreeMap;
 
 use common::{
-    chain::{ChainConfig, DelegationId, GenBlock, PoolId},
+    chain::{ChainConfig, DelegationId, GenBlock, PoolId, Transaction},
     primitives::{Amount, BlockHeight, Id},
 };
 
@@ -30,10 +30,47 @@ use serde_json::Value;
 use wallet::account::transaction_list::TransactionList;
 use wallet_rpc_lib::types::{Balances, PoolInfo};
 
+use crate::{
+    error::{AppError, Rejection},
+    fee::FeeEstimate,
+    request_tracker::RequestId,
+    token::{TokenInfo, TokenInfoCache},
+};
+
+/// Payload of an event sent in response to a command, tagged with the command's request id.
+#[derive(Debug, Clone, Serialize)]
+pub struct ResponseEvent<T> {
+    request_id: Option<RequestId>,
+    data: T,
+}
+
+impl<T> ResponseEvent<T> {
+    pub fn new(request_id: Option<RequestId>, data: T) -> Self {
+        ResponseEvent { request_id, data }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ErrorEvent {
+    request_id: Option<RequestId>,
+    #[serde(flatten)]
+    error: AppError,
+}
+
+impl ErrorEvent {
+    pub fn new(request_id: Option<RequestId>, error: AppError) -> Self {
+        ErrorEvent { request_id, error }
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct TransactionResult {
     transaction_info: TransactionInfo,
     serialized_tx: Value,
+    /// Set when the transaction was built but the console broadcast setting could not be
+    /// restored afterwards.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    broadcast_error: Option<AppError>,
 }
 
 impl TransactionResult {
@@ -45,8 +82,74 @@ impl TransactionResult {
         Ok(Self {
             transaction_info,
             serialized_tx,
+            broadcast_error: None,
         })
     }
+
+    pub fn with_broadcast_error(mut self, error: AppError) -> Self {
+        self.broadcast_error = Some(error);
+        self
+    }
+}
+
+/// A transaction built through the wallet console and what it costs, to be confirmed
+/// before `submit_transaction_wrapper`.
+#[derive(Debug, Clone, Serialize)]
+pub struct PreparedTransaction {
+    transaction: TransactionResult,
+    estimate: FeeEstimate,
+}
+
+impl PreparedTransaction {
+    pub fn new(transaction: TransactionResult, estimate: FeeEstimate) -> Self {
+        PreparedTransaction {
+            transaction,
+            estimate,
+        }
+    }
+
+    pub fn transaction(&self) -> &TransactionResult {
+        &self.transaction
+    }
+}
+
+/// A transaction carried between the watch-only and the cold wallet.
+#[derive(Debug, Clone, Serialize)]
+pub struct TransactionFileResult {
+    /// Hex encoded transaction
+    transaction: String,
+    summary: String,
+    /// Set once the transaction is fully signed, ready for `submit_transaction_wrapper`
+    signed: Option<TransactionResult>,
+}
+
+impl TransactionFileResult {
+    pub fn new(transaction: String, summary: String, signed: Option<TransactionResult>) -> Self {
+        TransactionFileResult {
+            transaction,
+            summary,
+            signed,
+        }
+    }
+}
+
+/// Outcome of broadcasting a transaction built outside of the app.
+#[derive(Debug, Clone, Serialize)]
+pub struct BroadcastResult {
+    tx_id: Id<Transaction>,
+    accepted: bool,
+    /// Why the node refused the transaction
+    rejection: Option<Rejection>,
+}
+
+impl BroadcastResult {
+    pub fn new(tx_id: Id<Transaction>, rejection: Option<Rejection>) -> Self {
+        BroadcastResult {
+            tx_id,
+            accepted: rejection.is_none(),
+            rejection,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -96,16 +199,29 @@ pub struct BalanceResult {
     wallet_id: WalletId,
     account_id: AccountId,
     balance: Balances,
+    /// Metadata of the tokens in `balance` that have been looked up so far
+    token_infos: BTreeMap<String, TokenInfo>,
 }
 
 impl BalanceResult {
-    pub fn new(wallet_id: WalletId, account_id: AccountId, balance: Balances) -> Self {
+    pub fn new(
+        wallet_id: WalletId,
+        account_id: AccountId,
+        balance: Balances,
+        token_infos: BTreeMap<String, TokenInfo>,
+    ) -> Self {
         BalanceResult {
             wallet_id,
             account_id,
             balance,
+            token_infos,
         }
     }
+
+    /// Takes the metadata of the tokens looked up since the balance was received.
+    pub fn update_token_infos(&mut self, token_infos: &TokenInfoCache) {
+        self.token_infos = token_infos.for_balance(&self.balance);
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -148,6 +264,10 @@ impl TransactionListResult {
             transaction_list,
         }
     }
+
+    pub fn skip(&self) -> usize {
+        self.transaction_list.skip
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -163,4 +283,8 @@ impl WalletBestBlockResult {
             block_info,
         }
     }
+
+    pub fn height(&self) -> BlockHeight {
+        self.block_info.1
+    }
 }
@@ -0,0 +1,258 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/node-gui-x/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::{Path, PathBuf};
+
+use node_gui_backend::WalletMode as BackendWalletMode;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    error::{AppError, ErrorCode},
+    network::Network,
+    storage,
+};
+
+/// Version of the settings file layout, bump it and add a step to `migrate` on every
+/// incompatible change.
+const SETTINGS_VERSION: u64 = 2;
+
+/// The wallet mode the node is started in, as selected in the UI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WalletMode {
+    Hot,
+    Cold,
+}
+
+impl WalletMode {
+    pub fn from_name(name: &str) -> Result<Self, AppError> {
+        match name {
+            "Hot" => Ok(WalletMode::Hot),
+            "Cold" => Ok(WalletMode::Cold),
+            _ => Err(AppError::invalid_input("Invalid wallet mode selection")),
+        }
+    }
+
+    pub fn to_backend(self) -> BackendWalletMode {
+        match self {
+            WalletMode::Hot => BackendWalletMode::Hot,
+            WalletMode::Cold => BackendWalletMode::Cold,
+        }
+    }
+}
+
+/// User preferences kept across restarts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    version: u64,
+    /// Network and wallet mode the node was last started with
+    pub network: Option<Network>,
+    pub wallet_mode: Option<WalletMode>,
+    /// Start the node on the last network without asking
+    pub auto_start: bool,
+    /// Reopen the most recent wallet once the node is running
+    pub reopen_last_wallet: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            version: SETTINGS_VERSION,
+            network: None,
+            wallet_mode: None,
+            auto_start: false,
+            reopen_last_wallet: false,
+        }
+    }
+}
+
+/// Brings settings written with layout `version` up to `SETTINGS_VERSION`, one version
+/// at a time.
+fn migrate(mut version: u64, mut settings: Value) -> Result<Value, AppError> {
+    while version < SETTINGS_VERSION {
+        match version {
+            // Version 1 kept the wallet mode as any string, an unknown one is dropped
+            1 => {
+                let known = settings
+                    .get("wallet_mode")
+                    .and_then(Value::as_str)
+                    .is_some_and(|mode| WalletMode::from_name(mode).is_ok());
+                if !known {
+                    if let Some(settings) = settings.as_object_mut() {
+                        settings.remove("wallet_mode");
+                    }
+                }
+            }
+            _ => {
+                return Err(AppError::new(
+                    ErrorCode::Storage,
+                    format!("No migration from settings version {version}"),
+                ))
+            }
+        }
+        version += 1;
+    }
+
+    if let Some(fields) = settings.as_object_mut() {
+        fields.insert("version".to_owned(), Value::from(SETTINGS_VERSION));
+    }
+    Ok(settings)
+}
+
+#[derive(Debug)]
+pub struct SettingsStore {
+    path: PathBuf,
+    settings: Settings,
+}
+
+impl SettingsStore {
+    /// Loads the settings from `path`, falling back to the defaults if the file is
+    /// missing. A file that cannot be read or migrated, or was written by a newer version,
+    /// is set aside first so saving the defaults does not destroy it.
+    pub fn load(path: PathBuf) -> Self {
+        let settings = storage::read_json::<Value>(&path)
+            .ok()
+            .flatten()
+            .and_then(|settings| Self::upgrade(&path, settings));
+
+        SettingsStore {
+            path,
+            settings: settings.unwrap_or_default(),
+        }
+    }
+
+    fn upgrade(path: &Path, settings: Value) -> Option<Settings> {
+        let version = settings.get("version").and_then(Value::as_u64).unwrap_or(1);
+        if version > SETTINGS_VERSION {
+            storage::set_aside(path, format!("Unsupported settings version {version}"));
+            return None;
+        }
+
+        let settings = migrate(version, settings).and_then(|settings| {
+            serde_json::from_value::<Settings>(settings)
+                .map_err(|e| AppError::new(ErrorCode::Storage, e.to_string()))
+        });
+        match settings {
+            Ok(settings) => Some(settings),
+            Err(e) => {
+                storage::set_aside(path, e.to_string());
+                None
+            }
+        }
+    }
+
+    pub fn get(&self) -> &Settings {
+        &self.settings
+    }
+
+    pub fn set(&mut self, mut settings: Settings) -> Result<(), AppError> {
+        settings.version = SETTINGS_VERSION;
+
+        self.settings = settings;
+        self.save()
+    }
+
+    /// Remembers the network and mode the node was started with, for `auto_start`.
+    pub fn set_last_node(
+        &mut self,
+        network: Network,
+        wallet_mode: WalletMode,
+    ) -> Result<(), AppError> {
+        self.settings.network = Some(network);
+        self.settings.wallet_mode = Some(wallet_mode);
+        self.save()
+    }
+
+    fn save(&self) -> Result<(), AppError> {
+        storage::write_json(&self.path, &self.settings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn temp_file(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("node-gui-x-settings-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(dir.join(format!("{name}.bak")));
+        path
+    }
+
+    #[test]
+    fn missing_file_loads_the_defaults() {
+        let store = SettingsStore::load(temp_file("missing.json"));
+        assert_eq!(store.get(), &Settings::default());
+        assert_eq!(store.get().version, SETTINGS_VERSION);
+        assert!(!store.get().auto_start);
+    }
+
+    #[test]
+    fn saved_settings_load_back() {
+        let path = temp_file("round_trip.json");
+        let mut store = SettingsStore::load(path.clone());
+        store.set_last_node(Network::Testnet, WalletMode::Cold).unwrap();
+        store
+            .set(Settings {
+                auto_start: true,
+                ..store.get().clone()
+            })
+            .unwrap();
+
+        let loaded = SettingsStore::load(path);
+        assert_eq!(loaded.get(), store.get());
+        assert_eq!(loaded.get().network, Some(Network::Testnet));
+        assert_eq!(loaded.get().wallet_mode, Some(WalletMode::Cold));
+        assert!(loaded.get().auto_start);
+    }
+
+    #[test]
+    fn newer_settings_are_set_aside() {
+        let path = temp_file("newer.json");
+        let newer = json!({ "version": SETTINGS_VERSION + 1, "auto_start": true });
+        std::fs::write(&path, newer.to_string()).unwrap();
+
+        let store = SettingsStore::load(path.clone());
+        assert_eq!(store.get(), &Settings::default());
+        assert!(!path.exists());
+        assert!(path.with_file_name("newer.json.bak").exists());
+    }
+
+    #[test]
+    fn version_one_settings_are_migrated() {
+        let migrated = migrate(1, json!({ "version": 1, "wallet_mode": "Warm" })).unwrap();
+        assert_eq!(migrated, json!({ "version": SETTINGS_VERSION }));
+
+        let path = temp_file("version_one.json");
+        let old = json!({
+            "version": 1,
+            "network": "Mainnet",
+            "wallet_mode": "Hot",
+            "reopen_last_wallet": true,
+        });
+        std::fs::write(&path, old.to_string()).unwrap();
+
+        let store = SettingsStore::load(path);
+        assert_eq!(store.get().version, SETTINGS_VERSION);
+        assert_eq!(store.get().network, Some(Network::Mainnet));
+        assert_eq!(store.get().wallet_mode, Some(WalletMode::Hot));
+        assert!(store.get().reopen_last_wallet);
+    }
+}
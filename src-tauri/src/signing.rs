@@ -0,0 +1,149 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/node-gui-x/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common::{
+    address::Address,
+    chain::{
+        signature::inputsig::arbitrary_message::ArbitraryMessageSignature, ChainConfig, Destination,
+    },
+};
+use serde::Serialize;
+
+use crate::error::{AppError, ErrorCode};
+
+/// Text the `account-sign-challenge-plain` console command prints before the signature.
+const SIGNATURE_HEADING: &str = "signature is";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MessageVerification {
+    valid: bool,
+    error: Option<String>,
+}
+
+impl MessageVerification {
+    pub fn is_valid(&self) -> bool {
+        self.valid
+    }
+}
+
+/// Checks a hex encoded signature made with `account-sign-challenge-plain` against the
+/// address that supposedly signed `message`.
+pub fn verify_message(
+    chain_config: &ChainConfig,
+    message: &str,
+    signature: &str,
+    address: &str,
+) -> MessageVerification {
+    let result = Address::<Destination>::from_string(chain_config, address)
+        .map_err(|e| format!("Invalid address: {e}"))
+        .and_then(|address| {
+            let signature = hex::decode(signature.trim())
+                .map_err(|e| format!("Invalid signature encoding: {e}"))?;
+            ArbitraryMessageSignature::from_data(signature)
+                .verify_signature(chain_config, &address.into_object(), message.as_bytes())
+                .map_err(|e| e.to_string())
+        });
+
+    MessageVerification {
+        valid: result.is_ok(),
+        error: result.err(),
+    }
+}
+
+/// Finds the hex encoded signature in the output of `account-sign-challenge-plain`, the
+/// first word after its heading.
+pub fn printed_signature(output: &str) -> Result<String, AppError> {
+    let unexpected = || {
+        AppError::new(
+            ErrorCode::Wallet,
+            format!("Unexpected console output: {output}"),
+        )
+    };
+
+    let heading = output.to_ascii_lowercase().find(SIGNATURE_HEADING).ok_or_else(unexpected)?;
+    let signature = output[heading + SIGNATURE_HEADING.len()..]
+        .split_whitespace()
+        .next()
+        .filter(|word| hex::decode(word).is_ok_and(|data| !data.is_empty()))
+        .ok_or_else(unexpected)?;
+    Ok(signature.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{address::pubkeyhash::PublicKeyHash, chain::config::create_unit_test_config};
+    use crypto::key::{KeyKind, PrivateKey};
+
+    use super::*;
+
+    /// Signs `message` like the wallet does, returning the address and the signature.
+    fn sign(chain_config: &ChainConfig, message: &str) -> (String, String) {
+        let mut rng = randomness::make_pseudo_rng();
+        let (private_key, public_key) =
+            PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr);
+        let destination = Destination::PublicKeyHash(PublicKeyHash::from(&public_key));
+        let signature = ArbitraryMessageSignature::produce_uniparty_signature(
+            &private_key,
+            &destination,
+            message.as_bytes(),
+            rng,
+        )
+        .unwrap();
+
+        let address = Address::new(chain_config, destination).unwrap().to_string();
+        (address, hex::encode(signature.as_raw()))
+    }
+
+    #[test]
+    fn signatures_verify_against_their_address() {
+        let chain_config = create_unit_test_config();
+        let (address, signature) = sign(&chain_config, "I own this address");
+
+        let verification =
+            verify_message(&chain_config, "I own this address", &signature, &address);
+        assert!(verification.is_valid(), "{:?}", verification.error);
+    }
+
+    #[test]
+    fn other_addresses_and_messages_do_not_verify() {
+        let chain_config = create_unit_test_config();
+        let (address, signature) = sign(&chain_config, "I own this address");
+        let (other_address, _) = sign(&chain_config, "I own this address");
+
+        let wrong_address = verify_message(
+            &chain_config,
+            "I own this address",
+            &signature,
+            &other_address,
+        );
+        assert!(!wrong_address.is_valid());
+        let tampered = verify_message(&chain_config, "I own that address", &signature, &address);
+        assert!(!tampered.is_valid());
+        let garbage = verify_message(&chain_config, "I own this address", "zz", &address);
+        assert!(garbage.error.unwrap().starts_with("Invalid signature encoding"));
+    }
+
+    #[test]
+    fn signature_is_read_after_its_heading() {
+        let signature = "ab".repeat(40);
+        let output = format!(
+            "The generated hex encoded signature is\n\n{signature}\n\nThe challenge was 0a0b"
+        );
+        assert_eq!(printed_signature(&output).unwrap(), signature);
+
+        assert!(printed_signature("0a0b0c").is_err());
+        assert!(printed_signature("The generated hex encoded signature is\n\nnone").is_err());
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/node-gui-x/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::{Path, PathBuf};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::error::{AppError, ErrorCode};
+
+/// Reads a JSON file of the config directory, `None` if there is none yet.
+///
+/// A file that cannot be read or parsed is renamed with a `.bak` extension before the
+/// error is returned, so that the next save does not destroy what it holds.
+pub fn read_json<T: DeserializeOwned>(path: &Path) -> Result<Option<T>, AppError> {
+    let data = match std::fs::read(path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(set_aside(path, e.to_string())),
+    };
+
+    serde_json::from_slice(&data)
+        .map(Some)
+        .map_err(|e| set_aside(path, e.to_string()))
+}
+
+/// Renames a file that cannot be used with a `.bak` extension, returning the error
+/// explaining why.
+pub fn set_aside(path: &Path, reason: String) -> AppError {
+    let backup = backup_path(path);
+    let moved = std::fs::rename(path, &backup);
+    let message = match moved {
+        Ok(()) => format!(
+            "{} could not be loaded and was moved to {}: {reason}",
+            path.display(),
+            backup.display()
+        ),
+        Err(e) => format!(
+            "{} could not be loaded: {reason}, nor moved: {e}",
+            path.display()
+        ),
+    };
+    AppError::new(ErrorCode::Storage, message)
+}
+
+/// Writes `value` to a temporary file next to `path` and renames it over `path`, so
+/// that an interrupted save never leaves a truncated file.
+pub fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), AppError> {
+    let data = serde_json::to_vec_pretty(value)
+        .map_err(|e| AppError::new(ErrorCode::Storage, e.to_string()))?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| AppError::new(ErrorCode::Storage, e.to_string()))?;
+    }
+
+    let temp_path = with_suffix(path, ".tmp");
+    std::fs::write(&temp_path, data)
+        .and_then(|()| std::fs::rename(&temp_path, path))
+        .map_err(|e| AppError::new(ErrorCode::Storage, format!("{}: {e}", path.display())))
+}
+
+fn backup_path(path: &Path) -> PathBuf {
+    with_suffix(path, ".bak")
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_file(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("node-gui-x-storage-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(backup_path(&path));
+        path
+    }
+
+    #[test]
+    fn written_files_read_back() {
+        let path = temp_file("round_trip.json");
+        assert_eq!(read_json::<Vec<u32>>(&path).unwrap(), None);
+
+        write_json(&path, &vec![1, 2, 3]).unwrap();
+        assert_eq!(read_json::<Vec<u32>>(&path).unwrap(), Some(vec![1, 2, 3]));
+        assert!(!with_suffix(&path, ".tmp").exists());
+    }
+
+    #[test]
+    fn corrupt_files_are_set_aside() {
+        let path = temp_file("corrupt.json");
+        std::fs::write(&path, "{ not json").unwrap();
+
+        let error = read_json::<Vec<u32>>(&path).unwrap_err();
+        assert_eq!(error.code(), ErrorCode::Storage);
+        assert!(!path.exists());
+        assert_eq!(std::fs::read(backup_path(&path)).unwrap(), b"{ not json");
+    }
+}
@@ -0,0 +1,250 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/node-gui-x/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use common::primitives::Amount;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use wallet_rpc_lib::types::Balances;
+
+use crate::{
+    console,
+    error::{AppError, ErrorCode},
+};
+
+/// Metadata of a fungible token, needed to display and parse its amounts.
+#[derive(Debug, Clone, Serialize)]
+pub struct TokenInfo {
+    token_id: String,
+    ticker: String,
+    decimals: u8,
+    metadata_uri: String,
+    authority: Option<String>,
+    circulating_supply: Option<Value>,
+    total_supply: Option<Value>,
+    is_locked: bool,
+    frozen: bool,
+}
+
+impl TokenInfo {
+    /// Reads the token info printed by the `token-info` console command.
+    pub fn from_json(token_id: &str, info: &Value) -> Result<Self, AppError> {
+        let info = info.get("FungibleToken").ok_or_else(|| {
+            AppError::invalid_input(format!("{token_id} is not a fungible token"))
+        })?;
+        let text = |field: &str| info.get(field).and_then(console::text);
+
+        let decimals = info
+            .get("number_of_decimals")
+            .and_then(Value::as_u64)
+            .and_then(|decimals| u8::try_from(decimals).ok())
+            .ok_or_else(|| {
+                AppError::new(ErrorCode::Wallet, format!("Unexpected token info: {info}"))
+            })?;
+
+        Ok(TokenInfo {
+            token_id: token_id.to_owned(),
+            ticker: text("token_ticker").unwrap_or_default().to_owned(),
+            decimals,
+            metadata_uri: text("metadata_uri").unwrap_or_default().to_owned(),
+            authority: text("authority").map(str::to_owned),
+            circulating_supply: info.get("circulating_supply").cloned(),
+            total_supply: info.get("total_supply").cloned(),
+            is_locked: info.get("is_locked").and_then(Value::as_bool).unwrap_or(false),
+            frozen: info.get("frozen").is_some_and(|frozen| *frozen != "NotFrozen"),
+        })
+    }
+
+    /// Checks that `amount` is a positive amount with at most the token's decimals.
+    pub fn parse_amount(&self, amount: &str) -> Result<Amount, AppError> {
+        Amount::from_fixedpoint_str(amount, self.decimals)
+            .filter(|amount| *amount != Amount::ZERO)
+            .ok_or_else(|| {
+                AppError::new(
+                    ErrorCode::InvalidAmount,
+                    format!("Invalid amount for {}: {amount}", self.ticker),
+                )
+            })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum TokenSupply {
+    Unlimited,
+    Lockable,
+    Fixed(String),
+}
+
+impl TokenSupply {
+    fn console_arg(&self) -> String {
+        match self {
+            TokenSupply::Unlimited => "unlimited".to_owned(),
+            TokenSupply::Lockable => "lockable".to_owned(),
+            TokenSupply::Fixed(amount) => format!("fixed({amount})"),
+        }
+    }
+}
+
+/// Parameters of a new fungible token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenIssuance {
+    pub ticker: String,
+    pub decimals: u8,
+    pub metadata_uri: String,
+    pub destination_address: String,
+    pub supply: TokenSupply,
+    pub freezable: bool,
+}
+
+impl TokenIssuance {
+    pub fn command_line(&self) -> Result<String, AppError> {
+        if self.ticker.is_empty() {
+            return Err(AppError::invalid_input("The ticker cannot be empty"));
+        }
+        if let TokenSupply::Fixed(amount) = &self.supply {
+            Amount::from_fixedpoint_str(amount, self.decimals)
+                .filter(|amount| *amount != Amount::ZERO)
+                .ok_or_else(|| {
+                    AppError::new(
+                        ErrorCode::InvalidAmount,
+                        format!("Invalid supply: {amount}"),
+                    )
+                })?;
+        }
+
+        Ok(console::command_line(
+            "token-issue-new",
+            &[
+                &self.ticker,
+                &self.decimals.to_string(),
+                &self.metadata_uri,
+                &self.destination_address,
+                &self.supply.console_arg(),
+                if self.freezable {
+                    "freezable"
+                } else {
+                    "not-freezable"
+                },
+            ],
+        ))
+    }
+}
+
+/// Operations the authority of a token can perform on it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum TokenAction {
+    Mint { address: String, amount: String },
+    Unmint { amount: String },
+    LockSupply,
+    Freeze { unfreezable: bool },
+    Unfreeze,
+    ChangeAuthority { address: String },
+    ChangeMetadataUri { metadata_uri: String },
+}
+
+impl TokenAction {
+    /// Builds the console command performing the action, checking amounts against `info`.
+    pub fn command_line(&self, info: &TokenInfo) -> Result<String, AppError> {
+        let token_id = info.token_id.as_str();
+        let command_line = match self {
+            TokenAction::Mint { address, amount } => {
+                info.parse_amount(amount)?;
+                console::command_line("token-mint", &[token_id, address, amount])
+            }
+            TokenAction::Unmint { amount } => {
+                info.parse_amount(amount)?;
+                console::command_line("token-unmint", &[token_id, amount])
+            }
+            TokenAction::LockSupply => console::command_line("token-lock-supply", &[token_id]),
+            TokenAction::Freeze { unfreezable } => console::command_line(
+                "token-freeze",
+                &[
+                    token_id,
+                    if *unfreezable {
+                        "unfreezable"
+                    } else {
+                        "not-unfreezable"
+                    },
+                ],
+            ),
+            TokenAction::Unfreeze => console::command_line("token-unfreeze", &[token_id]),
+            TokenAction::ChangeAuthority { address } => {
+                console::command_line("token-change-authority", &[token_id, address])
+            }
+            TokenAction::ChangeMetadataUri { metadata_uri } => {
+                console::command_line("token-change-metadata-uri", &[token_id, metadata_uri])
+            }
+        };
+        Ok(command_line)
+    }
+}
+
+/// Token infos looked up so far; entries are dropped when a management command changes
+/// the token.
+#[derive(Debug, Default)]
+pub struct TokenInfoCache {
+    infos: BTreeMap<String, TokenInfo>,
+    /// Tokens of the balances being looked up or that could not be, so that every
+    /// balance update does not start another lookup
+    looked_up: BTreeSet<String>,
+}
+
+impl TokenInfoCache {
+    pub fn get(&self, token_id: &str) -> Option<&TokenInfo> {
+        self.infos.get(token_id)
+    }
+
+    pub fn insert(&mut self, info: TokenInfo) {
+        self.infos.insert(info.token_id.clone(), info);
+    }
+
+    pub fn remove(&mut self, token_id: &str) {
+        self.infos.remove(token_id);
+        self.looked_up.remove(token_id);
+    }
+
+    /// Infos of the known tokens among those held in `balance`.
+    pub fn for_balance(&self, balance: &Balances) -> BTreeMap<String, TokenInfo> {
+        token_ids(balance)
+            .iter()
+            .filter_map(|token_id| self.get(token_id))
+            .map(|info| (info.token_id.clone(), info.clone()))
+            .collect()
+    }
+
+    /// Tokens held in `balance` which have to be looked up, they are not looked up again
+    /// afterwards.
+    pub fn start_lookups(&mut self, balance: &Balances) -> Vec<String> {
+        token_ids(balance)
+            .into_iter()
+            .filter(|token_id| !self.infos.contains_key(token_id))
+            .filter(|token_id| self.looked_up.insert(token_id.clone()))
+            .collect()
+    }
+}
+
+fn token_ids(balance: &Balances) -> Vec<String> {
+    // Token ids are only exposed as the keys of the serialized token map
+    let Ok(balance) = serde_json::to_value(balance) else {
+        return Vec::new();
+    };
+    balance
+        .get("tokens")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flat_map(|tokens| tokens.keys().cloned())
+        .collect()
+}
@@ -0,0 +1,322 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/node-gui-x/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common::{
+    address::dehexify::to_dehexified_json,
+    chain::{signature::inputsig::InputWitness, ChainConfig, SignedTransaction, Transaction},
+    primitives::{Amount, Id, Idable},
+};
+use node_gui_backend::{messages::WalletId, AccountId};
+use parity_scale_codec::DecodeAll;
+use serde::Serialize;
+use serde_json::Value;
+use wallet_types::partially_signed_transaction::PartiallySignedTransaction;
+
+use crate::{
+    error::{AppError, ErrorCode},
+    utxo::{coin_output, token_id, UtxoEntry},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum SignatureStatus {
+    Signed,
+    Unsigned,
+}
+
+/// Account of an open wallet which can spend an input.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct InputOwner {
+    wallet_id: WalletId,
+    account_id: AccountId,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DecodedInput {
+    input: Value,
+    /// Whether a signature is present, it is not checked against the spent output
+    signature: SignatureStatus,
+    /// Coin value of the spent output, known for outputs of the open wallets and for
+    /// the outputs a partially signed transaction carries
+    amount: Option<Amount>,
+    owner: Option<InputOwner>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DecodedOutput {
+    output: Value,
+    amount: Option<Amount>,
+    address: Option<String>,
+    token_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DecodedTransaction {
+    tx_id: Id<Transaction>,
+    /// Every input has a signature
+    signed: bool,
+    inputs: Vec<DecodedInput>,
+    outputs: Vec<DecodedOutput>,
+    /// Known only when the coin value of every input is known
+    fee: Option<Amount>,
+    /// The whole transaction, with the destinations as addresses
+    transaction: Value,
+}
+
+/// Unspent outputs of an account of an open wallet.
+pub type AccountUtxos = ((WalletId, AccountId), Vec<UtxoEntry>);
+
+impl DecodedTransaction {
+    /// Decodes a hex encoded transaction, signed, partially signed as produced by the
+    /// `account-sign-raw-transaction` console command, or not signed at all.
+    ///
+    /// Inputs are matched against `owned_utxos` to find their values and owners, so
+    /// inputs already spent by the wallets show as foreign ones.
+    pub fn decode(
+        chain_config: &ChainConfig,
+        hex: &str,
+        owned_utxos: &[AccountUtxos],
+    ) -> Result<Self, AppError> {
+        let data = hex::decode(hex.trim())
+            .map_err(|e| AppError::invalid_input(format!("Invalid hex: {e}")))?;
+
+        let (transaction, signatures, spent_amounts) = if let Ok(signed) =
+            SignedTransaction::decode_all(&mut data.as_slice())
+        {
+            let signatures =
+                signed.signatures().iter().map(|witness| signature_status(Some(witness)));
+            let spent_amounts = vec![None; signatures.len()];
+            (
+                signed.transaction().clone(),
+                signatures.collect(),
+                spent_amounts,
+            )
+        } else if let Ok(partial) = PartiallySignedTransaction::decode_all(&mut data.as_slice()) {
+            let signatures =
+                partial.witnesses().iter().map(|witness| signature_status(witness.as_ref()));
+            let spent_amounts = partial
+                .input_utxos()
+                .iter()
+                .map(|utxo| {
+                    let utxo = to_dehexified_json(chain_config, utxo.as_ref()?).ok()?;
+                    coin_output(&utxo).map(|(amount, _)| amount)
+                })
+                .collect();
+            (partial.tx().clone(), signatures.collect(), spent_amounts)
+        } else {
+            let transaction = Transaction::decode_all(&mut data.as_slice())
+                .map_err(|e| AppError::invalid_input(format!("Not an encoded transaction: {e}")))?;
+            let signatures = vec![SignatureStatus::Unsigned; transaction.inputs().len()];
+            let spent_amounts = vec![None; transaction.inputs().len()];
+            (transaction, signatures, spent_amounts)
+        };
+
+        let json = to_dehexified_json(chain_config, &transaction)
+            .map_err(|e| AppError::new(ErrorCode::Backend, e.to_string()))?;
+        let fields = json
+            .get("V1")
+            .ok_or_else(|| AppError::new(ErrorCode::Unsupported, "Unknown transaction version"))?;
+        let list =
+            |name: &str| fields.get(name).and_then(Value::as_array).cloned().unwrap_or_default();
+
+        let inputs = list("inputs")
+            .into_iter()
+            .zip(signatures)
+            .zip(spent_amounts)
+            .map(|((input, signature), spent_amount)| {
+                let owned = input.get("Utxo").and_then(|outpoint| {
+                    owned_utxos.iter().find_map(|((wallet_id, account_id), utxos)| {
+                        let utxo = utxos.iter().find(|utxo| utxo.outpoint() == outpoint)?;
+                        Some((
+                            utxo.amount(),
+                            InputOwner {
+                                wallet_id: *wallet_id,
+                                account_id: *account_id,
+                            },
+                        ))
+                    })
+                });
+                DecodedInput {
+                    input,
+                    signature,
+                    amount: owned.and_then(|(amount, _)| amount).or(spent_amount),
+                    owner: owned.map(|(_, owner)| owner),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let outputs = list("outputs")
+            .into_iter()
+            .map(|output| {
+                let coin = coin_output(&output);
+                DecodedOutput {
+                    amount: coin.map(|(amount, _)| amount),
+                    address: coin.map(|(_, address)| address.to_owned()),
+                    token_id: token_id(&output).map(str::to_owned),
+                    output,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let input_total =
+            inputs.iter().try_fold(Amount::ZERO, |total, input| total + input.amount?);
+        let output_total = outputs
+            .iter()
+            .filter_map(|output| output.amount)
+            .try_fold(Amount::ZERO, |total, amount| total + amount);
+        let fee = input_total
+            .zip(output_total)
+            .and_then(|(input_total, output_total)| input_total - output_total);
+
+        Ok(DecodedTransaction {
+            tx_id: transaction.get_id(),
+            signed: inputs.iter().all(|input| input.signature == SignatureStatus::Signed),
+            inputs,
+            outputs,
+            fee,
+            transaction: json,
+        })
+    }
+
+    pub fn tx_id(&self) -> Id<Transaction> {
+        self.tx_id
+    }
+
+    pub fn is_signed(&self) -> bool {
+        self.signed
+    }
+}
+
+fn signature_status(witness: Option<&InputWitness>) -> SignatureStatus {
+    match witness {
+        None | Some(InputWitness::NoSignature(_)) => SignatureStatus::Unsigned,
+        Some(_) => SignatureStatus::Signed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{
+        address::pubkeyhash::PublicKeyHash,
+        chain::{
+            config::create_unit_test_config,
+            output_value::OutputValue,
+            signature::{
+                inputsig::standard_signature::StandardInputSignature,
+                sighash::sighashtype::SigHashType,
+            },
+            Destination, OutPointSourceId, TxInput, TxOutput,
+        },
+        primitives::H256,
+    };
+    use parity_scale_codec::Encode;
+    use wallet_types::partially_signed_transaction::TxAdditionalInfo;
+
+    use super::*;
+
+    fn coins(chain_config: &ChainConfig, amount: &str) -> Amount {
+        Amount::from_fixedpoint_str(amount, chain_config.coin_decimals()).unwrap()
+    }
+
+    fn pay(chain_config: &ChainConfig, amount: &str) -> TxOutput {
+        let destination = Destination::PublicKeyHash(PublicKeyHash::repeat_byte(1));
+        TxOutput::Transfer(OutputValue::Coin(coins(chain_config, amount)), destination)
+    }
+
+    fn transaction(chain_config: &ChainConfig) -> Transaction {
+        let input = |byte| {
+            let source = OutPointSourceId::Transaction(Id::new(H256::repeat_byte(byte)));
+            TxInput::from_utxo(source, 0)
+        };
+        let outputs = vec![pay(chain_config, "7"), pay(chain_config, "5")];
+        Transaction::new(0, vec![input(1), input(2)], outputs).unwrap()
+    }
+
+    fn signature() -> InputWitness {
+        InputWitness::Standard(StandardInputSignature::new(
+            SigHashType::all(),
+            vec![1, 2, 3],
+        ))
+    }
+
+    fn signatures(decoded: &DecodedTransaction) -> Vec<SignatureStatus> {
+        decoded.inputs.iter().map(|input| input.signature).collect()
+    }
+
+    #[test]
+    fn unsigned_transactions_decode() {
+        let chain_config = create_unit_test_config();
+        let transaction = transaction(&chain_config);
+        let hex = hex::encode(transaction.encode());
+
+        let decoded = DecodedTransaction::decode(&chain_config, &hex, &[]).unwrap();
+        assert_eq!(decoded.tx_id(), transaction.get_id());
+        assert!(!decoded.is_signed());
+        assert_eq!(signatures(&decoded), [SignatureStatus::Unsigned; 2]);
+        assert_eq!(decoded.outputs[0].amount, Some(coins(&chain_config, "7")));
+        assert!(decoded.outputs[0].address.is_some());
+        // Nothing tells what the inputs are worth
+        assert_eq!(decoded.fee, None);
+    }
+
+    #[test]
+    fn partially_signed_transactions_carry_their_input_values() {
+        let chain_config = create_unit_test_config();
+        let transaction = transaction(&chain_config);
+        let destination = Destination::PublicKeyHash(PublicKeyHash::repeat_byte(1));
+        let partial = PartiallySignedTransaction::new(
+            transaction.clone(),
+            vec![Some(signature()), None],
+            vec![Some(pay(&chain_config, "10")), Some(pay(&chain_config, "5"))],
+            vec![Some(destination.clone()), Some(destination)],
+            None,
+            TxAdditionalInfo::new(),
+        )
+        .unwrap();
+
+        let hex = hex::encode(partial.encode());
+        let decoded = DecodedTransaction::decode(&chain_config, &hex, &[]).unwrap();
+        assert_eq!(decoded.tx_id(), transaction.get_id());
+        assert!(!decoded.is_signed());
+        assert_eq!(
+            signatures(&decoded),
+            [SignatureStatus::Signed, SignatureStatus::Unsigned]
+        );
+        assert_eq!(decoded.inputs[0].amount, Some(coins(&chain_config, "10")));
+        assert_eq!(decoded.fee, Some(coins(&chain_config, "3")));
+    }
+
+    #[test]
+    fn fully_signed_transactions_decode() {
+        let chain_config = create_unit_test_config();
+        let transaction = transaction(&chain_config);
+        let signed =
+            SignedTransaction::new(transaction.clone(), vec![signature(), signature()]).unwrap();
+
+        let hex = format!(" {}\n", hex::encode(signed.encode()));
+        let decoded = DecodedTransaction::decode(&chain_config, &hex, &[]).unwrap();
+        assert_eq!(decoded.tx_id(), transaction.get_id());
+        assert!(decoded.is_signed());
+        assert_eq!(signatures(&decoded), [SignatureStatus::Signed; 2]);
+    }
+
+    #[test]
+    fn invalid_data_is_rejected() {
+        let chain_config = create_unit_test_config();
+        for hex in ["not hex", "0", "00ff", ""] {
+            let error = DecodedTransaction::decode(&chain_config, hex, &[]).unwrap_err();
+            assert_eq!(error.code(), ErrorCode::InvalidInput, "{hex}");
+        }
+    }
+}
@@ -0,0 +1,147 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/node-gui-x/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{AppError, ErrorCode},
+    network::Network,
+    storage,
+};
+
+const TRANSACTION_FILE_VERSION: u32 = 1;
+
+/// File carrying a transaction between a watch-only wallet and an air-gapped cold wallet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionFile {
+    version: u32,
+    network: Network,
+    /// Hex encoded transaction, unsigned, partially signed or signed
+    transaction: String,
+}
+
+impl TransactionFile {
+    pub fn new(network: Network, transaction: String) -> Result<Self, AppError> {
+        let transaction = transaction.trim().to_owned();
+        if transaction.is_empty() || hex::decode(&transaction).is_err() {
+            return Err(AppError::invalid_input(
+                "The transaction must be hex encoded",
+            ));
+        }
+
+        Ok(TransactionFile {
+            version: TRANSACTION_FILE_VERSION,
+            network,
+            transaction,
+        })
+    }
+
+    /// Reads a transaction file, which must have been written for `network`.
+    pub fn read(path: &Path, network: Network) -> Result<Self, AppError> {
+        let data = std::fs::read(path).map_err(|e| {
+            AppError::new(
+                ErrorCode::Storage,
+                format!("Cannot read {}: {e}", path.display()),
+            )
+        })?;
+        let file = serde_json::from_slice::<TransactionFile>(&data)
+            .map_err(|e| AppError::invalid_input(format!("Not a transaction file: {e}")))?;
+
+        if file.version != TRANSACTION_FILE_VERSION {
+            return Err(AppError::invalid_input(format!(
+                "Unsupported transaction file version {}",
+                file.version
+            )));
+        }
+        if file.network != network {
+            return Err(AppError::invalid_input(format!(
+                "The transaction is for {:?}, not {network:?}",
+                file.network
+            )));
+        }
+
+        Self::new(file.network, file.transaction)
+    }
+
+    /// Writes the file through a temporary one, so that a cold wallet never picks up a
+    /// truncated transaction.
+    pub fn write(&self, path: &Path) -> Result<(), AppError> {
+        storage::write_json(path, self)
+    }
+
+    pub fn transaction(&self) -> &str {
+        &self.transaction
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_file(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "node-gui-x-transaction-file-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn written_files_read_back() {
+        let path = temp_file("round_trip.json");
+        let file = TransactionFile::new(Network::Testnet, " 0a0b0c \n".to_owned()).unwrap();
+        file.write(&path).unwrap();
+
+        let read = TransactionFile::read(&path, Network::Testnet).unwrap();
+        assert_eq!(read.transaction(), "0a0b0c");
+        assert_eq!(read.version, TRANSACTION_FILE_VERSION);
+    }
+
+    #[test]
+    fn files_of_another_network_are_rejected() {
+        let path = temp_file("other_network.json");
+        TransactionFile::new(Network::Mainnet, "00".to_owned())
+            .unwrap()
+            .write(&path)
+            .unwrap();
+
+        let error = TransactionFile::read(&path, Network::Testnet).unwrap_err();
+        assert_eq!(error.code(), ErrorCode::InvalidInput);
+    }
+
+    #[test]
+    fn invalid_files_are_rejected() {
+        assert!(TransactionFile::new(Network::Testnet, "".to_owned()).is_err());
+        assert!(TransactionFile::new(Network::Testnet, "not hex".to_owned()).is_err());
+
+        let path = temp_file("invalid.json");
+        std::fs::write(
+            &path,
+            r#"{"version": 2, "network": "Testnet", "transaction": "00"}"#,
+        )
+        .unwrap();
+        let error = TransactionFile::read(&path, Network::Testnet).unwrap_err();
+        assert_eq!(error.code(), ErrorCode::InvalidInput);
+
+        let missing = temp_file("missing.json");
+        let error = TransactionFile::read(&missing, Network::Testnet).unwrap_err();
+        assert_eq!(error.code(), ErrorCode::Storage);
+    }
+}
@@ -0,0 +1,260 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/node-gui-x/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use common::{chain::UtxoOutPoint, primitives::Amount};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::error::{AppError, ErrorCode};
+
+/// An unspent output of an account, as listed by the `account-utxos` console command.
+#[derive(Debug, Clone, Serialize)]
+pub struct UtxoEntry {
+    outpoint: Value,
+    /// Coin value, `None` for outputs holding tokens
+    amount: Option<Amount>,
+    address: Option<String>,
+    /// Timelocked or not mature yet, so it cannot be spent at the current block
+    locked: bool,
+    /// Blocks on top of and including the one holding the output, `None` when unknown
+    confirmations: Option<u64>,
+    output: Value,
+}
+
+impl UtxoEntry {
+    pub fn from_json(utxo: &Value, locked: bool) -> Result<Self, AppError> {
+        let (Some(outpoint), Some(output)) = (utxo.get("outpoint"), utxo.get("output")) else {
+            return Err(AppError::new(
+                ErrorCode::Wallet,
+                format!("Unexpected UTXO entry: {utxo}"),
+            ));
+        };
+
+        let coin = coin_output(output);
+
+        Ok(UtxoEntry {
+            outpoint: outpoint.clone(),
+            amount: coin.map(|(amount, _)| amount),
+            address: coin.map(|(_, address)| address.to_owned()),
+            locked,
+            confirmations: None,
+            output: output.clone(),
+        })
+    }
+
+    pub fn outpoint(&self) -> &Value {
+        &self.outpoint
+    }
+
+    pub fn amount(&self) -> Option<Amount> {
+        self.amount
+    }
+
+    pub fn address(&self) -> Option<&str> {
+        self.address.as_deref()
+    }
+
+    pub fn output(&self) -> &Value {
+        &self.output
+    }
+
+    /// Id of the transaction that created the output, `None` for block rewards.
+    fn tx_id(&self) -> Option<&str> {
+        self.outpoint.get("id")?.get("Transaction")?.as_str()
+    }
+}
+
+/// Counts the confirmations of `utxos` from the history of their account, as listed by
+/// the backend, and the height of the best block of the wallet.
+pub fn set_confirmations(utxos: &mut [UtxoEntry], history: &[Value], best_height: u64) {
+    let heights = history
+        .iter()
+        .filter_map(|tx| {
+            let height = tx.get("state")?.get("Confirmed")?.get(0)?.as_u64()?;
+            Some((tx.get("txid")?.as_str()?, height))
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    for utxo in utxos {
+        utxo.confirmations = utxo
+            .tx_id()
+            .and_then(|tx_id| heights.get(tx_id))
+            .and_then(|height| best_height.checked_sub(*height))
+            .map(|depth| depth + 1);
+    }
+}
+
+/// Looks up the outputs at `outpoints`, as listed by `list_utxos`, among the spendable
+/// outputs of an account.
+pub fn chosen(utxos: &[UtxoEntry], outpoints: &[UtxoOutPoint]) -> Result<Vec<UtxoEntry>, AppError> {
+    outpoints
+        .iter()
+        .map(|outpoint| {
+            let listed = serde_json::to_value(outpoint)
+                .map_err(|e| AppError::invalid_input(format!("Invalid outpoint: {e}")))?;
+            utxos
+                .iter()
+                .find(|utxo| utxo.outpoint == listed && utxo.amount.is_some())
+                .cloned()
+                .ok_or_else(|| {
+                    AppError::invalid_input(format!(
+                        "{listed} is not a spendable coin output of the account"
+                    ))
+                })
+        })
+        .collect()
+}
+
+/// The backend requests for staking and delegating have no input list, the wallet
+/// always selects the inputs itself.
+pub fn check_no_inputs(inputs: &[UtxoOutPoint], what: &str) -> Result<(), AppError> {
+    if inputs.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::invalid_input(format!(
+            "The inputs of {what} cannot be chosen, leave them to the wallet"
+        )))
+    }
+}
+
+/// Coin value and destination of a transfer output, `None` for any other output.
+pub fn coin_output(output: &Value) -> Option<(Amount, &str)> {
+    let fields = output
+        .get("Transfer")
+        .or_else(|| output.get("LockThenTransfer"))
+        .and_then(Value::as_array)?;
+    let atoms = fields.first()?.get("Coin")?.get("atoms")?.as_str()?.parse().ok()?;
+    let destination = fields.get(1)?.as_str()?;
+    Some((Amount::from_atoms(atoms), destination))
+}
+
+/// Id of the token held or issued by an output, `None` for coin outputs.
+pub fn token_id(output: &Value) -> Option<&str> {
+    if let Some(issuance) = output.get("IssueNft").and_then(Value::as_array) {
+        return issuance.first()?.as_str();
+    }
+    output
+        .get("Transfer")
+        .or_else(|| output.get("LockThenTransfer"))
+        .and_then(Value::as_array)?
+        .first()?
+        .get("TokenV1")?
+        .as_array()?
+        .first()?
+        .as_str()
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{
+        chain::{OutPointSourceId, Transaction},
+        primitives::{Id, H256},
+    };
+    use serde_json::json;
+
+    use super::*;
+
+    fn outpoint(tx: u64, index: u32) -> UtxoOutPoint {
+        let tx_id = Id::<Transaction>::new(H256::from_low_u64_be(tx));
+        UtxoOutPoint::new(OutPointSourceId::Transaction(tx_id), index)
+    }
+
+    fn coin(outpoint: &UtxoOutPoint, atoms: u128, locked: bool) -> UtxoEntry {
+        UtxoEntry::from_json(
+            &json!({
+                "outpoint": serde_json::to_value(outpoint).unwrap(),
+                "output": { "Transfer": [{ "Coin": { "atoms": atoms.to_string() } }, "own"] },
+            }),
+            locked,
+        )
+        .unwrap()
+    }
+
+    fn token(outpoint: &UtxoOutPoint) -> UtxoEntry {
+        UtxoEntry::from_json(
+            &json!({
+                "outpoint": serde_json::to_value(outpoint).unwrap(),
+                "output": { "Transfer": [{ "TokenV1": ["tmltk1", { "atoms": "5" }] }, "own"] },
+            }),
+            false,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn entries_report_amount_address_and_lock_state() {
+        let unlocked = coin(&outpoint(1, 0), 10, false);
+        let locked = coin(&outpoint(1, 1), 20, true);
+
+        assert_eq!(unlocked.amount(), Some(Amount::from_atoms(10)));
+        assert_eq!(unlocked.address(), Some("own"));
+        assert!(!unlocked.locked);
+        assert!(locked.locked);
+        assert_eq!(token(&outpoint(1, 2)).amount(), None);
+
+        let error = UtxoEntry::from_json(&json!({ "output": {} }), false).unwrap_err();
+        assert_eq!(error.code(), ErrorCode::Wallet);
+    }
+
+    #[test]
+    fn chosen_outputs_are_found_in_the_given_order() {
+        let utxos = [coin(&outpoint(1, 0), 10, false), coin(&outpoint(2, 1), 20, false)];
+
+        let picked = chosen(&utxos, &[outpoint(2, 1), outpoint(1, 0)]).unwrap();
+        assert_eq!(
+            picked.iter().map(UtxoEntry::amount).collect::<Vec<_>>(),
+            [Some(Amount::from_atoms(20)), Some(Amount::from_atoms(10))]
+        );
+    }
+
+    #[test]
+    fn unknown_and_token_outputs_cannot_be_chosen() {
+        let utxos = [coin(&outpoint(1, 0), 10, false), token(&outpoint(3, 0))];
+
+        let error = chosen(&utxos, &[outpoint(1, 1)]).unwrap_err();
+        assert_eq!(error.code(), ErrorCode::InvalidInput);
+        let error = chosen(&utxos, &[outpoint(3, 0)]).unwrap_err();
+        assert_eq!(error.code(), ErrorCode::InvalidInput);
+    }
+
+    #[test]
+    fn confirmations_count_from_the_block_of_the_transaction() {
+        let mut utxos = [
+            coin(&outpoint(1, 0), 10, false),
+            coin(&outpoint(2, 0), 20, false),
+            coin(&outpoint(3, 0), 30, false),
+        ];
+        let txid = |tx: u64| coin(&outpoint(tx, 0), 0, false).tx_id().unwrap().to_owned();
+        let history = [
+            json!({ "txid": txid(1), "state": { "Confirmed": [100, 0, 0] } }),
+            json!({ "txid": txid(2), "state": "InMempool" }),
+        ];
+
+        set_confirmations(&mut utxos, &history, 104);
+
+        assert_eq!(utxos[0].confirmations, Some(5));
+        assert_eq!(utxos[1].confirmations, None);
+        assert_eq!(utxos[2].confirmations, None);
+    }
+
+    #[test]
+    fn explicit_inputs_are_rejected_where_the_wallet_picks_them() {
+        assert!(check_no_inputs(&[], "a stake").is_ok());
+        let error = check_no_inputs(&[outpoint(1, 0)], "a stake").unwrap_err();
+        assert_eq!(error.code(), ErrorCode::InvalidInput);
+    }
+}
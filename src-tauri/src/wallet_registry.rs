@@ -0,0 +1,249 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/node-gui-x/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use node_gui_backend::messages::{EncryptionState, WalletId, WalletInfo};
+use serde::{Deserialize, Serialize};
+use wallet_types::wallet_type::WalletType;
+
+use crate::{
+    error::{AppError, ErrorCode},
+    storage,
+};
+
+const MAX_RECENT_WALLETS: usize = 10;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenWalletEntry {
+    wallet_id: WalletId,
+    file_path: PathBuf,
+    wallet_type: WalletType,
+    encryption: EncryptionState,
+    account_count: usize,
+    selected: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentWallet {
+    file_path: PathBuf,
+    wallet_type: WalletType,
+    /// Seconds since the Unix epoch
+    last_opened: u64,
+}
+
+/// Wallets currently opened in the backend, plus the list of recently opened wallet
+/// files which is persisted across restarts.
+#[derive(Debug)]
+pub struct WalletRegistry {
+    open_wallets: BTreeMap<WalletId, OpenWalletEntry>,
+    selected_wallet: Option<WalletId>,
+    recent_wallets: Vec<RecentWallet>,
+    recent_wallets_path: PathBuf,
+}
+
+impl WalletRegistry {
+    /// Loads the recent wallets from `recent_wallets_path`; a missing file just means
+    /// there are no recent wallets yet, an unreadable one is set aside by
+    /// `storage::read_json`.
+    pub fn load(recent_wallets_path: PathBuf) -> Self {
+        let recent_wallets =
+            storage::read_json(&recent_wallets_path).ok().flatten().unwrap_or_default();
+
+        WalletRegistry {
+            open_wallets: BTreeMap::new(),
+            selected_wallet: None,
+            recent_wallets,
+            recent_wallets_path,
+        }
+    }
+
+    /// Records a wallet that was just opened or created and persists the recent list.
+    pub fn add_wallet(&mut self, wallet_info: &WalletInfo) -> Result<(), AppError> {
+        self.open_wallets.insert(
+            wallet_info.wallet_id,
+            OpenWalletEntry {
+                wallet_id: wallet_info.wallet_id,
+                file_path: wallet_info.path.clone(),
+                wallet_type: wallet_info.wallet_type,
+                encryption: wallet_info.encryption,
+                account_count: wallet_info.accounts.len(),
+                selected: false,
+            },
+        );
+
+        self.add_recent_wallet(&wallet_info.path, wallet_info.wallet_type)
+    }
+
+    /// Moves a wallet file to the top of the recent list, keeping the list short.
+    fn add_recent_wallet(
+        &mut self,
+        file_path: &Path,
+        wallet_type: WalletType,
+    ) -> Result<(), AppError> {
+        let last_opened = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        self.recent_wallets.retain(|recent| recent.file_path != file_path);
+        self.recent_wallets.insert(
+            0,
+            RecentWallet {
+                file_path: file_path.to_owned(),
+                wallet_type,
+                last_opened,
+            },
+        );
+        self.recent_wallets.truncate(MAX_RECENT_WALLETS);
+
+        self.save_recent_wallets()
+    }
+
+    pub fn remove_wallet(&mut self, wallet_id: WalletId) {
+        self.open_wallets.remove(&wallet_id);
+        if self.selected_wallet == Some(wallet_id) {
+            self.selected_wallet = None;
+        }
+    }
+
+    pub fn set_encryption(&mut self, wallet_id: WalletId, encryption: EncryptionState) {
+        if let Some(entry) = self.open_wallets.get_mut(&wallet_id) {
+            entry.encryption = encryption;
+        }
+    }
+
+    pub fn add_account(&mut self, wallet_id: WalletId) {
+        if let Some(entry) = self.open_wallets.get_mut(&wallet_id) {
+            entry.account_count += 1;
+        }
+    }
+
+    pub fn select_wallet(&mut self, wallet_id: WalletId) -> Result<(), AppError> {
+        if !self.open_wallets.contains_key(&wallet_id) {
+            return Err(
+                AppError::new(ErrorCode::UnknownWallet, "Wallet is not open")
+                    .with_context(Some(wallet_id), None),
+            );
+        }
+        self.selected_wallet = Some(wallet_id);
+        Ok(())
+    }
+
+    pub fn open_wallets(&self) -> Vec<OpenWalletEntry> {
+        self.open_wallets
+            .values()
+            .map(|entry| OpenWalletEntry {
+                selected: self.selected_wallet == Some(entry.wallet_id),
+                ..entry.clone()
+            })
+            .collect()
+    }
+
+    pub fn recent_wallets(&self) -> &[RecentWallet] {
+        &self.recent_wallets
+    }
+
+    pub fn remove_recent_wallet(&mut self, file_path: &Path) -> Result<(), AppError> {
+        self.recent_wallets.retain(|recent| recent.file_path != file_path);
+        self.save_recent_wallets()
+    }
+
+    /// Forgets the open wallets, used once the backend holding them has stopped.
+    pub fn clear_open_wallets(&mut self) {
+        self.open_wallets.clear();
+        self.selected_wallet = None;
+    }
+
+    fn save_recent_wallets(&self) -> Result<(), AppError> {
+        storage::write_json(&self.recent_wallets_path, &self.recent_wallets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_registry(name: &str) -> WalletRegistry {
+        let dir =
+            std::env::temp_dir().join(format!("node-gui-x-wallet-registry-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        let _ = std::fs::remove_file(&path);
+        WalletRegistry::load(path)
+    }
+
+    fn recent_files(registry: &WalletRegistry) -> Vec<String> {
+        registry
+            .recent_wallets()
+            .iter()
+            .map(|recent| recent.file_path.display().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn recent_wallets_are_listed_most_recent_first_and_saved() {
+        let mut registry = empty_registry("ordering.json");
+        registry.add_recent_wallet(Path::new("a.dat"), WalletType::Hot).unwrap();
+        registry.add_recent_wallet(Path::new("b.dat"), WalletType::Cold).unwrap();
+        registry.add_recent_wallet(Path::new("c.dat"), WalletType::Hot).unwrap();
+        assert_eq!(recent_files(&registry), ["c.dat", "b.dat", "a.dat"]);
+
+        let loaded = WalletRegistry::load(registry.recent_wallets_path.clone());
+        assert_eq!(recent_files(&loaded), ["c.dat", "b.dat", "a.dat"]);
+        assert_eq!(loaded.recent_wallets()[1].wallet_type, WalletType::Cold);
+    }
+
+    #[test]
+    fn reopened_wallets_move_to_the_top_once() {
+        let mut registry = empty_registry("dedupe.json");
+        for file in ["a.dat", "b.dat", "a.dat"] {
+            registry.add_recent_wallet(Path::new(file), WalletType::Hot).unwrap();
+        }
+        assert_eq!(recent_files(&registry), ["a.dat", "b.dat"]);
+
+        registry.remove_recent_wallet(Path::new("a.dat")).unwrap();
+        assert_eq!(recent_files(&registry), ["b.dat"]);
+    }
+
+    #[test]
+    fn recent_wallets_are_capped() {
+        let mut registry = empty_registry("cap.json");
+        for index in 0..MAX_RECENT_WALLETS + 3 {
+            let file = format!("{index}.dat");
+            registry.add_recent_wallet(Path::new(&file), WalletType::Hot).unwrap();
+        }
+
+        let files = recent_files(&registry);
+        assert_eq!(files.len(), MAX_RECENT_WALLETS);
+        assert_eq!(
+            files.first().unwrap(),
+            &format!("{}.dat", MAX_RECENT_WALLETS + 2)
+        );
+        assert_eq!(files.last().unwrap(), "3.dat");
+    }
+
+    #[test]
+    fn only_open_wallets_can_be_selected() {
+        let mut registry = empty_registry("select.json");
+        let error = registry.select_wallet(WalletId::new()).unwrap_err();
+        assert_eq!(error.code(), ErrorCode::UnknownWallet);
+        assert!(registry.open_wallets().is_empty());
+    }
+}
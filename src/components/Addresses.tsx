@@ -14,10 +14,8 @@
 // limitations under the License.
 
 import { AiOutlineCopy } from "react-icons/ai";
-import { listen } from "@tauri-apps/api/event";
-import { invoke } from "@tauri-apps/api/core";
 import AddressIcon from "../assets/account_icon.png";
-import { notify } from "../utils/util";
+import { errorMessage, invokeAndWait, notify } from "../utils/util";
 
 const Addresses = (props: {
   isLoading: boolean;
@@ -37,38 +35,27 @@ const Addresses = (props: {
   const handleAddAddress = async () => {
     try {
       props.setIsLoading(true);
-      const unsubscribe = await listen("NewAddress", (event) => {
-        const newAddress: {
-          wallet_id: number;
-          account_id: number;
-          index: number;
-          address: string;
-        } = event.payload as {
-          wallet_id: number;
-          account_id: number;
-          index: number;
-          address: string;
-        };
-        if (newAddress) {
-          props.handleUpdateCurrentAccount(
-            newAddress.wallet_id,
-            newAddress.account_id,
-            newAddress.index.toString(),
-            newAddress.address
-          );
-          notify("New address added", "success");
-        }
-        unsubscribe();
-      });
-      await invoke("new_address_wrapper", {
+      const newAddress = await invokeAndWait<{
+        wallet_id: number;
+        account_id: number;
+        index: number;
+        address: string;
+      }>("NewAddress", "new_address_wrapper", {
         request: { wallet_id: props.walletId, account_id: props.accountId },
       });
+      props.handleUpdateCurrentAccount(
+        newAddress.wallet_id,
+        newAddress.account_id,
+        newAddress.index.toString(),
+        newAddress.address
+      );
+      notify("New address added", "success");
       props.setIsLoading(false);
     } catch (err: any) {
       const regex = /Wallet error: (.+)/;
-      const errorMessage = new String(err).match(regex);
-      if (errorMessage) {
-        notify(errorMessage[1], "error");
+      const message = errorMessage(err).match(regex);
+      if (message) {
+        notify(message[1], "error");
       }
       props.setIsLoading(false);
     }
@@ -17,9 +17,8 @@ import { useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { exit } from "@tauri-apps/plugin-process";
 import { AiOutlineCopy } from "react-icons/ai";
-import { listen } from "@tauri-apps/api/event";
 import { RiDeleteBinLine } from "react-icons/ri";
-import { notify } from "../utils/util";
+import { errorMessage, invokeAndWait } from "../utils/util";
 
 import { AccountType, ConsoleCommand, WalletInfo } from "../types/Types";
 
@@ -49,52 +48,49 @@ const Console = (props: {
 
   const handleSendCommand = async () => {
     try {
-      const unsubscribe = await listen("ConsoleResponse", (event) => {
-        setCommandHistory((history) => [...history, command]);
-        const consoleResult = event.payload as ConsoleCommand;
-        if (typeof consoleResult === "string") {
-          setText((text) => text + "\n" + command + "\n" + consoleResult);
-        } else if ("ClearScreen" in consoleResult) {
-          setText("");
-        } else if ("PrintHistory" in consoleResult) {
-          setText(
-            (text) => text + "\n" + command + "\n" + commandHistory.join("\n")
-          );
-        } else if ("ClearHistory" in consoleResult) {
-          setCommandHistory([]);
-        } else if ("Exit" in consoleResult) {
-          handleExit();
-        } else if ("SetStatus" in consoleResult) {
-          setText(
-            (text) =>
-              text +
-              "\n" +
-              command +
-              "\n" +
-              consoleResult.SetStatus.print_message
-          );
-        } else if ("Print" in consoleResult) {
-          setText((text) => text + "\n" + command + "\n" + consoleResult.Print);
-        } else {
-          setText(
-            (text) =>
-              text + "\n" + command + "\n" + JSON.stringify(consoleResult)
-          );
+      const consoleResult = await invokeAndWait<ConsoleCommand>(
+        "ConsoleResponse",
+        "handle_console_command_wrapper",
+        {
+          request: {
+            wallet_id: props.currentWallet?.wallet_id
+              ? props.currentWallet?.wallet_id
+              : 0,
+            account_id: props.currentAccountId,
+            command: command,
+          },
         }
-        setCommand("");
-        unsubscribe();
-      });
-      await invoke("handle_console_command_wrapper", {
-        request: {
-          wallet_id: props.currentWallet?.wallet_id
-            ? props.currentWallet?.wallet_id
-            : 0,
-          account_id: props.currentAccountId,
-          command: command,
-        },
-      });
+      );
+      setCommandHistory((history) => [...history, command]);
+      if (typeof consoleResult === "string") {
+        setText((text) => text + "\n" + command + "\n" + consoleResult);
+      } else if ("ClearScreen" in consoleResult) {
+        setText("");
+      } else if ("PrintHistory" in consoleResult) {
+        setText(
+          (text) => text + "\n" + command + "\n" + commandHistory.join("\n")
+        );
+      } else if ("ClearHistory" in consoleResult) {
+        setCommandHistory([]);
+      } else if ("Exit" in consoleResult) {
+        handleExit();
+      } else if ("SetStatus" in consoleResult) {
+        setText(
+          (text) =>
+            text + "\n" + command + "\n" + consoleResult.SetStatus.print_message
+        );
+      } else if ("Print" in consoleResult) {
+        setText((text) => text + "\n" + command + "\n" + consoleResult.Print);
+      } else {
+        setText(
+          (text) => text + "\n" + command + "\n" + JSON.stringify(consoleResult)
+        );
+      }
+      setCommand("");
     } catch (error) {
-      notify(new String(error).toString(), "error");
+      setCommandHistory((history) => [...history, command]);
+      setText((text) => text + "\n" + command + "\n" + errorMessage(error));
+      setCommand("");
     }
   };
 
@@ -13,9 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-import { invoke } from "@tauri-apps/api/core";
 import { useState } from "react";
-import { listen } from "@tauri-apps/api/event";
 import { AiOutlineCopy } from "react-icons/ai";
 import {
   AccountType,
@@ -23,7 +21,13 @@ import {
   DelegationBalancesType,
   WalletInfo,
 } from "../types/Types";
-import { encodeToHash, notify, DECIMAL } from "../utils/util";
+import {
+  encodeToHash,
+  errorMessage,
+  invokeAndWait,
+  notify,
+  DECIMAL,
+} from "../utils/util";
 import { IoCloseSharp } from "react-icons/io5";
 
 const Delegation = (props: {
@@ -54,28 +58,24 @@ const Delegation = (props: {
     props.setLoadingMessage("Depositing to delegation. Please wait.");
     props.setIsLoading(true);
     try {
-      const unsubscribe = await listen("DelegateStaking", (event) => {
-        const transactionInfo = event.payload as TransactionData;
-        if (transactionInfo) {
-          setTransactionInfo(transactionInfo);
-          setShowConfirmTransactionModal(true);
+      const transactionInfo = await invokeAndWait<TransactionData>(
+        "DelegateStaking",
+        "delegate_staking_wrapper",
+        {
+          request: {
+            wallet_id: props.currentWallet?.wallet_id
+              ? props.currentWallet.wallet_id
+              : 0,
+            account_id: props.currentAccountId,
+            delegation_id: currentDelegationId,
+            delegation_amount: depositAmount.toString(),
+          },
         }
-        unsubscribe();
-      });
-      await invoke("delegate_staking_wrapper", {
-        request: {
-          wallet_id: props.currentWallet?.wallet_id
-            ? props.currentWallet.wallet_id
-            : 0,
-          account_id: props.currentAccountId,
-          delegation_id: currentDelegationId,
-          delegation_amount: depositAmount.toString(),
-        },
-      });
-
-      props.setIsLoading(false);
+      );
+      setTransactionInfo(transactionInfo);
+      setShowConfirmTransactionModal(true);
     } catch (error) {
-      notify(new String(error).toString(), "error");
+      notify(errorMessage(error), "error");
     }
     props.setIsLoading(false);
   };
@@ -84,27 +84,25 @@ const Delegation = (props: {
     props.setIsLoading(true);
     setShowWithdrawModal(false);
     try {
-      const unsubscribe = await listen("SendDelegationToAddress", (event) => {
-        const transactionInfo = event.payload as TransactionData;
-        if (transactionInfo) {
-          setTransactionInfo(transactionInfo);
-          setShowConfirmTransactionModal(true);
+      const transactionInfo = await invokeAndWait<TransactionData>(
+        "SendDelegationToAddress",
+        "send_delegation_to_address_wrapper",
+        {
+          request: {
+            wallet_id: props.currentWallet?.wallet_id
+              ? props.currentWallet.wallet_id
+              : 0,
+            account_id: props.currentAccountId,
+            address: withdrawAddress,
+            amount: withdrawAmount.toString(),
+            delegation_id: currentDelegationId,
+          },
         }
-        unsubscribe();
-      });
-      await invoke("send_delegation_to_address_wrapper", {
-        request: {
-          wallet_id: props.currentWallet?.wallet_id
-            ? props.currentWallet.wallet_id
-            : 0,
-          account_id: props.currentAccountId,
-          address: withdrawAddress,
-          amount: withdrawAmount.toString(),
-          delegation_id: currentDelegationId,
-        },
-      });
+      );
+      setTransactionInfo(transactionInfo);
+      setShowConfirmTransactionModal(true);
     } catch (error) {
-      notify(new String(error).toString(), "error");
+      notify(errorMessage(error), "error");
     }
     props.setIsLoading(false);
   };
@@ -123,56 +121,45 @@ const Delegation = (props: {
     props.setLoadingMessage("Creating Delegation. Please wait");
     props.setIsLoading(true);
     try {
-      const unsubscribe = await listen("CreateDelegation", (event) => {
-        const transactionInfo = event.payload as TransactionData;
-        if (transactionInfo) {
-          setTransactionInfo(transactionInfo);
-          setShowConfirmTransactionModal(true);
-          props.setIsLoading(false);
+      const transactionInfo = await invokeAndWait<TransactionData>(
+        "CreateDelegation",
+        "create_delegation_wrapper",
+        {
+          request: {
+            wallet_id: props.currentWallet?.wallet_id
+              ? props.currentWallet.wallet_id
+              : 0,
+            account_id: props.currentAccountId,
+            pool_id: poolAddress,
+            delegation_address: delegationAddress,
+          },
         }
-        unsubscribe();
-      });
-      await invoke("create_delegation_wrapper", {
-        request: {
-          wallet_id: props.currentWallet?.wallet_id
-            ? props.currentWallet.wallet_id
-            : 0,
-          account_id: props.currentAccountId,
-          pool_id: poolAddress,
-          delegation_address: delegationAddress,
-        },
-      });
+      );
+      setTransactionInfo(transactionInfo);
+      setShowConfirmTransactionModal(true);
     } catch (error) {
-      notify(new String(error).toString(), "error");
-      props.setIsLoading(false);
+      notify(errorMessage(error), "error");
     }
+    props.setIsLoading(false);
   };
 
   const handleConfirmTransaction = async () => {
     props.setLoadingMessage("Confirming transaction. Please wait.");
     props.setIsLoading(true);
     try {
-      const unsubscribe = await listen("Broadcast", (event) => {
-        const result = event.payload as number;
-        if (result === props.currentWallet?.wallet_id) {
-          notify("Transaction submitted successfully!", "success");
-          setShowSuccessModal(true);
-        }
-        unsubscribe();
-        setShowConfirmTransactionModal(false);
-      });
-      await invoke("submit_transaction_wrapper", {
+      await invokeAndWait("Broadcast", "submit_transaction_wrapper", {
         request: {
           wallet_id: transactionInfo?.transaction_info.wallet_id,
           tx: transactionInfo?.transaction_info,
         },
       });
-
-      props.setIsLoading(false);
+      notify("Transaction submitted successfully!", "success");
+      setShowSuccessModal(true);
+      setShowConfirmTransactionModal(false);
     } catch (error) {
-      props.setIsLoading(false);
-      notify(new String(error).toString(), "error");
+      notify(errorMessage(error), "error");
     }
+    props.setIsLoading(false);
   };
 
   return (
@@ -14,11 +14,25 @@
 // limitations under the License.
 
 import { useState } from "react";
-import { listen } from "@tauri-apps/api/event";
 import { IoCloseSharp } from "react-icons/io5";
 import { invoke } from "@tauri-apps/api/core";
-import { encodeToHash, notify } from "../utils/util";
-import { AccountType, TransactionData } from "../types/Types";
+import { open, save } from "@tauri-apps/plugin-dialog";
+import {
+  encodeToHash,
+  errorMessage,
+  invokeAndWait,
+  notify,
+} from "../utils/util";
+import {
+  AccountType,
+  Contact,
+  FeeEstimate,
+  FeeRate,
+  PaymentOutput,
+  PayoutPreview,
+  PreparedTransaction,
+  TransactionData,
+} from "../types/Types";
 const Send = (props: {
   isLoading: boolean;
   setIsLoading: (isLoading: boolean) => void;
@@ -30,33 +44,141 @@ const Send = (props: {
 }) => {
   const [address, setAddress] = useState("");
   const [amount, setAmount] = useState("");
+  const [feeRate, setFeeRate] = useState<FeeRate>("Normal");
+  const [contacts, setContacts] = useState<Contact[]>([]);
   const [transactionInfo, setTransactionInfo] = useState<
     TransactionData | undefined
   >();
+  const [feeEstimate, setFeeEstimate] = useState<FeeEstimate | undefined>();
+  const [payouts, setPayouts] = useState<PaymentOutput[] | undefined>();
   const [showConfirmModal, setShowConfirmModal] = useState(false);
   const [showSuccessModal, setShowSuccessModal] = useState(false);
   const handleSend = async (event: React.FormEvent<HTMLFormElement>) => {
     event.preventDefault();
     event.stopPropagation();
     try {
-      await invoke("send_amount_wrapper", {
+      props.setLoadingMessage("Building the transaction. Please wait.");
+      props.setIsLoading(true);
+      // The estimate carries the signed transaction, which is the one submitted
+      const prepared: PreparedTransaction = await invoke("estimate_fee", {
         request: {
           wallet_id: props.walletId,
           account_id: props.accountId,
           amount: amount,
           address: address,
+          fee_rate: feeRate,
         },
+      }).finally(() => props.setIsLoading(false));
+      setPayouts([{ address, amount }]);
+      setFeeEstimate(prepared.estimate);
+      confirm(prepared.transaction);
+    } catch (error) {
+      notify(errorMessage(error), "error");
+    }
+  };
+
+  const confirm = (transactionResult: TransactionData) => {
+    setTransactionInfo(transactionResult);
+    setShowConfirmModal(true);
+  };
+
+  const handleExportTransaction = async () => {
+    try {
+      const filePath = await save({
+        filters: [{ name: "Transaction file", extensions: ["json"] }],
       });
-      const unsubscribe = await listen("SendAmount", (event) => {
-        const transactionResult = event.payload as TransactionData;
-        if (transactionResult) {
-          setTransactionInfo(transactionResult);
-          setShowConfirmModal(true);
+      if (!filePath || !transactionInfo) {
+        return;
+      }
+      await invoke("export_transaction_file", {
+        walletId: props.walletId,
+        accountId: props.accountId,
+        filePath,
+        transaction: transactionInfo.transaction_info.tx.tx,
+      });
+      notify("Transaction saved for signing", "info");
+      setShowConfirmModal(false);
+    } catch (error) {
+      notify(errorMessage(error), "error");
+    }
+  };
+
+  const handleAddressChange = async (value: string) => {
+    setAddress(value);
+    try {
+      setContacts(value ? await invoke("find_contacts", { query: value }) : []);
+    } catch (error) {
+      setContacts([]);
+    }
+  };
+
+  const handleSendAll = async () => {
+    try {
+      props.setLoadingMessage("Building the transaction. Please wait.");
+      props.setIsLoading(true);
+      const prepared: PreparedTransaction = await invoke(
+        "sweep_account_wrapper",
+        {
+          request: {
+            wallet_id: props.walletId,
+            account_id: props.accountId,
+            address: address,
+            fee_rate: feeRate,
+          },
         }
-        unsubscribe();
+      ).finally(() => props.setIsLoading(false));
+      setPayouts([
+        {
+          address,
+          amount: prepared.estimate.payment.atoms,
+        },
+      ]);
+      setFeeEstimate(prepared.estimate);
+      confirm(prepared.transaction);
+    } catch (error) {
+      notify(errorMessage(error), "error");
+    }
+  };
+
+  const handleImportCsv = async () => {
+    try {
+      const filePath = await open({
+        filters: [{ name: "CSV file", extensions: ["csv"] }],
+      });
+      if (!filePath) {
+        return;
+      }
+      const preview: PayoutPreview = await invoke("preview_payout_csv", {
+        filePath,
       });
+      if (!preview.valid) {
+        notify(
+          preview.errors
+            .map((error) => `Line ${error.line}: ${error.message}`)
+            .join("\n"),
+          "error"
+        );
+        return;
+      }
+      const outputs = preview.rows.map((row) => row.output);
+      props.setLoadingMessage("Building the payout transaction. Please wait.");
+      props.setIsLoading(true);
+      const prepared: PreparedTransaction = await invoke(
+        "batch_send_wrapper",
+        {
+          request: {
+            wallet_id: props.walletId,
+            account_id: props.accountId,
+            outputs,
+            fee_rate: feeRate,
+          },
+        }
+      ).finally(() => props.setIsLoading(false));
+      setPayouts(outputs);
+      setFeeEstimate(prepared.estimate);
+      confirm(prepared.transaction);
     } catch (error) {
-      notify(new String(error).toString(), "error");
+      notify(errorMessage(error), "error");
     }
   };
 
@@ -64,26 +186,19 @@ const Send = (props: {
     props.setLoadingMessage("Confirming transaction. Please wait.");
     props.setIsLoading(true);
     try {
-      const unsubscribe = await listen("Broadcast", (event) => {
-        const result = event.payload as number;
-        if (result === props.walletId) {
-          notify("Transaction submitted successfully!", "success");
-          setShowConfirmModal(false);
-          setShowSuccessModal(true);
-        }
-        unsubscribe();
-        props.setIsLoading(false);
-      });
-      await invoke("submit_transaction_wrapper", {
+      await invokeAndWait("Broadcast", "submit_transaction_wrapper", {
         request: {
           wallet_id: transactionInfo?.transaction_info.wallet_id,
           tx: transactionInfo?.transaction_info,
         },
       });
+      notify("Transaction submitted successfully!", "success");
+      setShowConfirmModal(false);
+      setShowSuccessModal(true);
     } catch (error) {
-      notify(new String(error).toString(), "error");
-      props.setIsLoading(false);
+      notify(errorMessage(error), "error");
     }
+    props.setIsLoading(false);
   };
   return (
     <div className="container mx-auto p-2">
@@ -152,20 +267,45 @@ const Send = (props: {
             </div>
             <div>
               <p className="text-start">BEGIN OF OUTPUTS</p>
-              <p className="text-start whitespace-nowrap">
-                -Transfer({address}, {amount}),
-              </p>
-              <p className="text-start">
-                -Transfer({props.currentAccount?.addresses[0]},{" "}
-                {props.currentAccount?.balance.coins.atoms
-                  ? props.currentAccount.balance.coins.atoms
-                  : 0 - parseInt(amount)}
-                ),
-              </p>
+              {payouts ? (
+                <>
+                  {payouts.map((payout, index) => (
+                    <p key={index} className="text-start whitespace-nowrap">
+                      -Transfer({payout.address}, {payout.amount}),
+                    </p>
+                  ))}
+                  {feeEstimate && (
+                    <p className="text-start">
+                      -Change({feeEstimate.change.atoms}),
+                    </p>
+                  )}
+                </>
+              ) : (
+                <>
+                  <p className="text-start whitespace-nowrap">
+                    -Transfer({address}, {amount}),
+                  </p>
+                  <p className="text-start">
+                    -Transfer({props.currentAccount?.addresses[0]},{" "}
+                    {props.currentAccount?.balance.coins.atoms
+                      ? props.currentAccount.balance.coins.atoms
+                      : 0 - parseInt(amount)}
+                    ),
+                  </p>
+                </>
+              )}
             </div>
             <div>
               <p className="text-start text-bold">END OF OUTPUTS</p>
             </div>
+            {feeEstimate && (
+              <div>
+                <p className="text-start">Fee: {feeEstimate.fee.atoms}</p>
+                <p className="text-start">
+                  Total debit: {feeEstimate.total_debit.atoms}
+                </p>
+              </div>
+            )}
             <button
               className="bg-green-400 text-black w-full px-2 py-1 rounded-lg hover:bg-[#000000] hover:text-green-400 transition duration-200"
               onClick={() => {
@@ -175,6 +315,12 @@ const Send = (props: {
             >
               Confirm and Broadcast
             </button>
+            <button
+              className="bg-green-400 text-black w-full px-2 py-1 rounded-lg hover:bg-[#000000] hover:text-green-400 transition duration-200"
+              onClick={handleExportTransaction}
+            >
+              Save to File for Cold Signing
+            </button>
           </div>
         </div>
       )}
@@ -212,7 +358,8 @@ const Send = (props: {
           <input
             type="text"
             value={address}
-            onChange={(e) => setAddress(e.target.value)}
+            onChange={(e) => handleAddressChange(e.target.value)}
+            list="contacts"
             placeholder="Enter address"
             className="w-80 shadow-md focus:outline-none focus:ring-2 focus:ring-blue-500 border border-gray-300 rounded-lg py-2 px-3"
           />
@@ -225,6 +372,13 @@ const Send = (props: {
           >
             <IoCloseSharp />
           </button>
+          <datalist id="contacts">
+            {contacts.map((contact) => (
+              <option key={contact.address} value={contact.address}>
+                {contact.label}
+              </option>
+            ))}
+          </datalist>
         </div>
 
         <p className="text-lg font-semibold text-start w-80">Amount</p>
@@ -248,12 +402,54 @@ const Send = (props: {
           />
         </div>
 
+        <p className="text-lg font-semibold text-start w-80">Fee</p>
+        <select
+          value={typeof feeRate === "string" ? feeRate : "PerKb"}
+          onChange={(e) =>
+            setFeeRate(
+              e.target.value === "PerKb"
+                ? { PerKb: "" }
+                : (e.target.value as FeeRate)
+            )
+          }
+          className="w-80 shadow-md focus:outline-none focus:ring-2 focus:ring-blue-500 border border-gray-300 rounded-lg py-2 px-3"
+        >
+          <option value="Low">Low</option>
+          <option value="Normal">Normal</option>
+          <option value="High">High</option>
+          <option value="PerKb">Coins per kB</option>
+        </select>
+        {typeof feeRate !== "string" && (
+          <input
+            type="number"
+            value={feeRate.PerKb}
+            min={0}
+            onChange={(e) => setFeeRate({ PerKb: e.target.value })}
+            placeholder="Enter coins per 1000 bytes"
+            className="w-80 shadow-md focus:outline-none focus:ring-2 focus:ring-blue-500 border border-gray-300 rounded-lg py-2 px-3"
+          />
+        )}
+
         <button
           type="submit"
           className="py-1 px-4 rounded-lg bg-[#69EE96] text-[#000000] hover:text-[#69EE96] hover:bg-black "
         >
           Send
         </button>
+        <button
+          type="button"
+          onClick={handleSendAll}
+          className="py-1 px-4 rounded-lg bg-[#69EE96] text-[#000000] hover:text-[#69EE96] hover:bg-black "
+        >
+          Send all
+        </button>
+        <button
+          type="button"
+          onClick={handleImportCsv}
+          className="py-1 px-4 rounded-lg bg-[#69EE96] text-[#000000] hover:text-[#69EE96] hover:bg-black "
+        >
+          Import CSV
+        </button>
       </form>
     </div>
   );
@@ -14,11 +14,15 @@
 // limitations under the License.
 
 import { useState } from "react";
-import { invoke } from "@tauri-apps/api/core";
-import { listen } from "@tauri-apps/api/event";
 import { AiOutlineCopy } from "react-icons/ai";
 import { IoCloseSharp } from "react-icons/io5";
-import { encodeToHash, notify, DECIMAL } from "../utils/util";
+import {
+  encodeToHash,
+  errorMessage,
+  invokeAndWait,
+  notify,
+  DECIMAL,
+} from "../utils/util";
 import {
   AccountType,
   WalletInfo,
@@ -64,41 +68,20 @@ const Staking = (props: {
           : "Starting Staking, Please wait."
       );
       props.setIsLoading(true);
-      const unsubscribe = await listen("ToggleStaking", (event) => {
-        if (Array.isArray(event.payload)) {
-          const [wallet_id, account_id, enabled] = event.payload;
-          if (
-            wallet_id === props.currentWalletId &&
-            account_id === props.currentAccountId &&
-            event.payload
-          ) {
-            setIsStakingStarted(enabled);
-            props.handleUpdateStakingState(enabled);
-            notify(enabled ? "Staking started" : "Staking stopped", "success");
-          }
-        }
-        unsubscribe();
-      });
-      await invoke("toggle_staking_wrapper", {
-        request: {
-          wallet_id: props.currentWalletId ? props.currentWalletId : 0,
-          account_id: props.currentAccountId ? props.currentAccountId : 0,
-          enabled: !isStakingStarted,
-        },
-      });
-
-      await invoke("toggle_staking_wrapper", {
+      const [, , enabled] = await invokeAndWait<
+        [wallet_id: number, account_id: number, enabled: boolean]
+      >("ToggleStaking", "toggle_staking_wrapper", {
         request: {
           wallet_id: props.currentWalletId ? props.currentWalletId : 0,
           account_id: props.currentAccountId ? props.currentAccountId : 0,
           enabled: !isStakingStarted,
         },
       });
-
-      props.setIsLoading(false);
+      setIsStakingStarted(enabled);
+      props.handleUpdateStakingState(enabled);
+      notify(enabled ? "Staking started" : "Staking stopped", "success");
     } catch (error) {
-      const errorMessage = new String(error);
-      notify(errorMessage.toString(), "error");
+      notify(errorMessage(error), "error");
     }
     props.setIsLoading(false);
   };
@@ -106,41 +89,24 @@ const Staking = (props: {
     try {
       props.setLoadingMessage("Decommissioning Staking Pool. Please wait.");
       props.setIsLoading(true);
-      const unsubscribe = await listen("DecommissionPool", (event) => {
-        const transactionResult = event.payload as TransactionData;
-        if (transactionResult) {
-          const transactionResult = event.payload as TransactionData;
-          if (transactionResult) {
-            setTransactionInfo(transactionResult);
-            setShowConfirmTransactionModal(true);
-          }
+      const transactionResult = await invokeAndWait<TransactionData>(
+        "DecommissionPool",
+        "decommission_pool_wrapper",
+        {
+          request: {
+            wallet_id: props.currentWalletId ? props.currentWalletId : 0,
+            account_id: props.currentAccountId ? props.currentAccountId : 0,
+            pool_id: currentPoolId,
+            output_address: receiveAddress,
+          },
         }
-        unsubscribe();
-      });
-      await invoke("decommission_pool_wrapper", {
-        request: {
-          wallet_id: props.currentWalletId ? props.currentWalletId : 0,
-          account_id: props.currentAccountId ? props.currentAccountId : 0,
-          pool_id: currentPoolId,
-          output_address: receiveAddress,
-        },
-      });
-      await invoke("decommission_pool_wrapper", {
-        request: {
-          wallet_id: props.currentWalletId ? props.currentWalletId : 0,
-          account_id: props.currentAccountId ? props.currentAccountId : 0,
-          pool_id: currentPoolId,
-          output_address: receiveAddress,
-        },
-      });
-      props.setIsLoading(false);
+      );
+      setTransactionInfo(transactionResult);
+      setShowConfirmTransactionModal(true);
     } catch (error) {
-      const regex = /Wallet error: (.+)/;
-      const errorMessage = new String(error).match(regex);
-      if (errorMessage) {
-        notify(errorMessage[1], "error");
-      }
+      notify(errorMessage(error), "error");
     }
+    props.setIsLoading(false);
     setShowDecommissionModal(false);
   };
 
@@ -149,31 +115,24 @@ const Staking = (props: {
       props.setLoadingMessage("Creating Staking Pool. Please wait");
       props.setIsLoading(true);
 
-      const unsubscribe = await listen("StakeAmount", (event) => {
-        const transactionResult = event.payload as TransactionData;
-        if (transactionResult) {
-          setTransactionInfo(transactionResult);
-          setShowConfirmTransactionModal(true);
+      const transactionResult = await invokeAndWait<TransactionData>(
+        "StakeAmount",
+        "stake_amount_wrapper",
+        {
+          request: {
+            wallet_id: props.currentWalletId ? props.currentWalletId : 0,
+            account_id: props.currentAccountId ? props.currentAccountId : 0,
+            pledge_amount: pledgeAmount.toString(),
+            mpt: marginRatio.toString(),
+            cost_per_block: costPerBlock.toString(),
+            decommission_address: decommissionAddress,
+          },
         }
-        unsubscribe();
-      });
-      await invoke("stake_amount_wrapper", {
-        request: {
-          wallet_id: props.currentWalletId ? props.currentWalletId : 0,
-          account_id: props.currentAccountId ? props.currentAccountId : 0,
-          pledge_amount: pledgeAmount.toString(),
-          mpt: marginRatio.toString(),
-          cost_per_block: costPerBlock.toString(),
-          decommission_address: decommissionAddress,
-        },
-      });
-      props.setIsLoading(false);
+      );
+      setTransactionInfo(transactionResult);
+      setShowConfirmTransactionModal(true);
     } catch (error) {
-      const regex = /Wallet error: (.+)/;
-      const errorMessage = new String(error).match(regex);
-      if (errorMessage) {
-        notify(errorMessage[1], "error");
-      }
+      notify(errorMessage(error), "error");
     }
     props.setIsLoading(false);
   };
@@ -181,32 +140,19 @@ const Staking = (props: {
     try {
       props.setLoadingMessage("Confirming transaction. Please wait.");
       props.setIsLoading(true);
-      const unsubscribe = await listen("Broadcast", (event) => {
-        const result = event.payload as number;
-        if (result === props.currentWallet?.wallet_id) {
-          notify("Transaction submitted successfully!", "success");
-          setShowConfirmTransactionModal(false);
-          setShowSuccessModal(true);
-        }
-        unsubscribe();
-      });
-      await invoke("submit_transaction_wrapper", {
-        request: {
-          wallet_id: transactionInfo?.transaction_info.wallet_id,
-          tx: transactionInfo?.transaction_info,
-        },
-      });
-      await invoke("submit_transaction_wrapper", {
+      await invokeAndWait("Broadcast", "submit_transaction_wrapper", {
         request: {
           wallet_id: transactionInfo?.transaction_info.wallet_id,
           tx: transactionInfo?.transaction_info,
         },
       });
-      props.setIsLoading(false);
+      notify("Transaction submitted successfully!", "success");
+      setShowConfirmTransactionModal(false);
+      setShowSuccessModal(true);
     } catch (error) {
-      notify(new String(error).toString(), "error");
-      props.setIsLoading(false);
+      notify(errorMessage(error), "error");
     }
+    props.setIsLoading(false);
   };
 
   return (
@@ -14,7 +14,6 @@
 // limitations under the License.
 
 import { useEffect, useState } from "react";
-import { listen } from "@tauri-apps/api/event";
 import Addresses from "./Addresses";
 import Console from "./Console";
 import Delegation from "./Delegation";
@@ -22,8 +21,7 @@ import Send from "./Send";
 import Staking from "./Staking";
 import Transactions from "./Transactions";
 import { IoCloseSharp } from "react-icons/io5";
-import { invoke } from "@tauri-apps/api/core";
-import { notify } from "../utils/util";
+import { errorMessage, invokeAndWait, notify } from "../utils/util";
 import {
   AccountType,
   ChainInfoType,
@@ -106,38 +104,32 @@ const WalletActions = (props: {
         ...additionalParams,
       };
 
-      await invoke("update_encryption_wrapper", { request });
-
-      const unsubscribe = await listen("UpdateEncryption", (event) => {
-        const [wallet_id, encryptionState] = event.payload as [
-          wallet_id: number,
-          encryptionState: string
-        ];
-        if (encryptionState) {
-          setWalletState(encryptionState);
-          props.handleUpdateCurrentWalletEncryptionState(
-            wallet_id,
-            encryptionState
-          );
-          setShowEncryptWalletModal(false);
-          setShowUnlockModal(false);
-          notify(
-            `Wallet ${
-              action === "set_password"
-                ? "Encrypted"
-                : action === "remove_password"
-                ? "Decrypted"
-                : action === "lock"
-                ? "Locked"
-                : "Unlocked"
-            } successfully.`,
-            "success"
-          );
-        }
-        unsubscribe();
-      });
+      const [wallet_id, encryptionState] = await invokeAndWait<
+        [wallet_id: number, encryptionState: string]
+      >("UpdateEncryption", "update_encryption_wrapper", { request });
+      if (encryptionState) {
+        setWalletState(encryptionState);
+        props.handleUpdateCurrentWalletEncryptionState(
+          wallet_id,
+          encryptionState
+        );
+        setShowEncryptWalletModal(false);
+        setShowUnlockModal(false);
+        notify(
+          `Wallet ${
+            action === "set_password"
+              ? "Encrypted"
+              : action === "remove_password"
+              ? "Decrypted"
+              : action === "lock"
+              ? "Locked"
+              : "Unlocked"
+          } successfully.`,
+          "success"
+        );
+      }
     } catch (error) {
-      notify(new String(error).toString(), "error");
+      notify(errorMessage(error), "error");
     }
   };
   const handleCloseWallet = async (wallet_id: number) => {
@@ -145,22 +137,18 @@ const WalletActions = (props: {
       props.setIsLoading(true);
       props.setLoadingMessage("Closing wallet. Please wait.");
 
-      const unsubscribe = await listen("CloseWallet", (event) => {
-        const closeWalletResult = event.payload as number;
-        if (closeWalletResult !== undefined) {
-          props.handleRemoveWallet(closeWalletResult);
-          notify("Wallet closed successfully.", "success");
-        }
-        props.setIsLoading(false);
-        unsubscribe();
-      });
-      await invoke("close_wallet_wrapper", {
-        walletId: wallet_id,
-      });
+      const closeWalletResult = await invokeAndWait<number>(
+        "CloseWallet",
+        "close_wallet_wrapper",
+        { walletId: wallet_id }
+      );
+      props.handleRemoveWallet(closeWalletResult);
+      notify("Wallet closed successfully.", "success");
+      props.setIsLoading(false);
     } catch (error) {
       props.setIsLoading(false);
       props.setIsLoading(false);
-      notify(new String(error).toString(), "error");
+      notify(errorMessage(error), "error");
     }
   };
 
@@ -20,8 +20,6 @@ import { Menu } from "@tauri-apps/api/menu";
 import { getCurrentWindow } from "@tauri-apps/api/window";
 import { UnlistenFn, listen } from "@tauri-apps/api/event";
 import { save, open } from "@tauri-apps/plugin-dialog";
-import * as bip39 from "@scure/bip39";
-import { wordlist } from "@scure/bip39/wordlists/english";
 import { RiInformation2Line } from "react-icons/ri";
 import { PiShareNetworkBold } from "react-icons/pi";
 import { ToastContainer } from "react-toastify";
@@ -40,7 +38,7 @@ import WalletIcon from "../assets/wallet_icon.png";
 import AccountIcon from "../assets/account_icon.png";
 import MintlayerIcon from "../assets/mintlayer_icon.png";
 
-import { notify } from "../utils/util";
+import { errorMessage, invokeAndWait, notify } from "../utils/util";
 import SummaryTab from "../components/Summary";
 import NetworkingTab from "../components/Networking";
 import {
@@ -52,7 +50,12 @@ import {
   P2p,
   PeerConnected,
   PoolInfoType,
+  ErrorEvent,
+  RecentWallet,
+  Settings,
+  MnemonicValidation,
   StakingBalancesType,
+  TokenInfo,
   TransactionType,
   WalletInfo,
 } from "../types/Types";
@@ -138,6 +141,7 @@ function Home() {
   const [currentTab, setCurrentTab] = useState("summary");
   const [activeTab, setActiveTab] = useState("transactions");
   const [mnemonic, setMnemonic] = useState("");
+  const [passphrase, setPassphrase] = useState("");
   const [accountName, setAccountName] = useState("");
   const [stakingBalances, setStakingBalances] = useState<StakingBalancesType[]>(
     () => {
@@ -153,6 +157,8 @@ function Home() {
   });
 
   const [maturityPeriod, setMaturityPeriod] = useState(0);
+  const [settings, setSettings] = useState<Settings | undefined>();
+  const lastWalletReopened = useRef(false);
 
   const [showMnemonicModal, setShowMnemonicModal] = useState(false);
   const [showRecoverWalletModal, setShowRecoverWalletModal] = useState(false);
@@ -375,6 +381,68 @@ function Home() {
     fetchMaturityPeriod();
   }, [chainInfo]);
 
+  useEffect(() => {
+    const loadSettings = async () => {
+      try {
+        const settings: Settings = await invoke("get_settings");
+        setSettings(settings);
+      } catch (error) {
+        notify(errorMessage(error), "error");
+      }
+    };
+    loadSettings();
+  }, []);
+
+  // Runs once the settings are loaded, against the wallets open by then
+  useEffect(() => {
+    if (
+      !settings?.reopen_last_wallet ||
+      walletsInfo.length > 0 ||
+      lastWalletReopened.current
+    ) {
+      return;
+    }
+    lastWalletReopened.current = true;
+    const reopenLastWallet = async () => {
+      try {
+        const recentWallets: RecentWallet[] = await invoke(
+          "list_recent_wallets"
+        );
+        const lastWallet = recentWallets.find(
+          (wallet) => wallet.wallet_type === walletMode
+        );
+        if (lastWallet) {
+          const walletInfo = await invokeAndWait<WalletInfo>(
+            "OpenWallet",
+            "add_open_wallet_wrapper",
+            {
+              request: {
+                file_path: lastWallet.file_path,
+                wallet_type: lastWallet.wallet_type,
+              },
+            }
+          );
+          setWalletsInfo((prevWallets) => [...prevWallets, walletInfo]);
+        }
+      } catch (error) {
+        notify(errorMessage(error), "error");
+      }
+    };
+    reopenLastWallet();
+  }, [settings, walletsInfo, walletMode]);
+
+  const updateSettings = async (changes: Partial<Settings>) => {
+    try {
+      // The network and mode are saved by the backend on every start
+      const current: Settings = await invoke("get_settings");
+      const updated = { ...current, ...changes };
+      await invoke("set_settings", { settings: updated });
+      setSettings(updated);
+    } catch (error) {
+      notify(errorMessage(error), "error");
+    }
+  };
+
   const contextMenu = Menu.new({
     items: [],
   });
@@ -411,9 +479,13 @@ function Home() {
 
   const errorListener = async () => {
     try {
-      const unsubscribe = await listen("Error", (event) => {
+      const unsubscribe = await listen<ErrorEvent>("Error", (event) => {
+        // Errors of a request are reported by the code waiting for it
+        if (event.payload.request_id !== null) {
+          return;
+        }
         const regex = /Wallet error: (.+)/;
-        const errorMessage = new String(event.payload).match(regex);
+        const errorMessage = event.payload.message.match(regex);
         if (errorMessage) {
           notify(errorMessage[1], "error");
         }
@@ -444,6 +516,7 @@ function Home() {
           wallet_id: number;
           account_id: number;
           balance: BalanceType;
+          token_infos: Record<string, TokenInfo>;
         };
 
         if (newBalances && newBalances.wallet_id && newBalances.account_id) {
@@ -639,9 +712,9 @@ function Home() {
       console.error("Error setting up delegation balance listener:", error);
     }
   };
-  const createNewWallet = () => {
+  const createNewWallet = async () => {
     try {
-      const newMnemonic = bip39.generateMnemonic(wordlist, 256);
+      const newMnemonic: string = await invoke("generate_mnemonic");
       setMnemonic(newMnemonic);
       setShowMnemonicModal(true);
     } catch (error) {
@@ -660,22 +733,22 @@ function Home() {
 
       if (path) {
         try {
-          const unsubscribe = await listen("ImportWallet", (event) => {
-            const walletInfo = event.payload as WalletInfo;
-            if (walletInfo) {
-              setWalletsInfo([...walletsInfo, walletInfo]);
-              notify("Wallet created successfully!", "success");
+          // A wallet with a passphrase is written first and then opened
+          const walletInfo = await invokeAndWait<WalletInfo>(
+            passphrase ? "OpenWallet" : "ImportWallet",
+            "add_create_wallet_wrapper",
+            {
+              request: {
+                file_path: path,
+                mnemonic: mnemonic,
+                passphrase: passphrase || null,
+                import: true,
+                wallet_type: walletMode,
+              },
             }
-            unsubscribe();
-          });
-          await invoke("add_create_wallet_wrapper", {
-            request: {
-              file_path: path,
-              mnemonic: mnemonic,
-              import: true,
-              wallet_type: walletMode,
-            },
-          });
+          );
+          setWalletsInfo((prevWallets) => [...prevWallets, walletInfo]);
+          notify("Wallet created successfully!", "success");
         } catch (invokeError) {
           notify("Error in creating wallet!", "error");
           console.error(
@@ -684,6 +757,7 @@ function Home() {
           );
         }
         setMnemonic("");
+        setPassphrase("");
 
         setShowMnemonicModal(false);
       } else {
@@ -700,6 +774,15 @@ function Home() {
   const handleRecoverWallet = async () => {
     setLoadingMessage("Recovering wallet. Please wait.");
     try {
+      const validation: MnemonicValidation = await invoke(
+        "validate_mnemonic",
+        { mnemonic: mnemonic }
+      );
+      if (!validation.valid) {
+        notify(validation.error ?? "Invalid mnemonic", "error");
+        return;
+      }
+
       const path = await save({
         defaultPath: "key.dat",
         filters: [{ name: "Key files", extensions: [] }],
@@ -709,24 +792,22 @@ function Home() {
         setLoading(true);
 
         try {
-          const unsubscribe = await listen("ImportWallet", (event) => {
-            const walletInfo: WalletInfo = event.payload as WalletInfo;
-
-            if (walletInfo) {
-              setWalletsInfo((prevWallets) => [...prevWallets, walletInfo]);
-              notify("Wallet recovered successfully", "success");
+          // A wallet with a passphrase is written first and then opened
+          const walletInfo = await invokeAndWait<WalletInfo>(
+            passphrase ? "OpenWallet" : "ImportWallet",
+            "add_create_wallet_wrapper",
+            {
+              request: {
+                file_path: path,
+                mnemonic: mnemonic,
+                passphrase: passphrase || null,
+                import: false,
+                wallet_type: walletMode,
+              },
             }
-            setLoading(false);
-            unsubscribe();
-          });
-          await invoke("add_create_wallet_wrapper", {
-            request: {
-              file_path: path,
-              mnemonic: mnemonic,
-              import: false,
-              wallet_type: walletMode,
-            },
-          });
+          );
+          setWalletsInfo((prevWallets) => [...prevWallets, walletInfo]);
+          notify("Wallet recovered successfully", "success");
         } catch (invokeError) {
           notify("Error in recovering wallet!", "error");
           console.error(
@@ -734,7 +815,9 @@ function Home() {
             invokeError instanceof Error ? invokeError.message : invokeError
           );
         }
+        setLoading(false);
         setMnemonic("");
+        setPassphrase("");
         setShowRecoverWalletModal(false);
       } else {
         console.error("No file selected");
@@ -763,25 +846,21 @@ function Home() {
       });
 
       if (filePath) {
-        const unsubscribe = await listen("OpenWallet", (event) => {
-          const walletInfo: WalletInfo = event.payload as WalletInfo;
-
-          if (walletInfo) {
-            setWalletsInfo((prevWallets) => [...prevWallets, walletInfo]);
-            notify("Wallet opened successfully", "success");
+        const walletInfo = await invokeAndWait<WalletInfo>(
+          "OpenWallet",
+          "add_open_wallet_wrapper",
+          {
+            request: {
+              file_path: filePath,
+              wallet_type: walletMode,
+            },
           }
-
-          unsubscribe();
-        });
-        await invoke("add_open_wallet_wrapper", {
-          request: {
-            file_path: filePath,
-            wallet_type: walletMode,
-          },
-        });
+        );
+        setWalletsInfo((prevWallets) => [...prevWallets, walletInfo]);
+        notify("Wallet opened successfully", "success");
       }
     } catch (error) {
-      console.error("Error opening wallet:", error);
+      notify(errorMessage(error), "error");
     }
   };
 
@@ -878,26 +957,20 @@ function Home() {
     setLoading(true);
     setLoadingMessage("Creating new account. Please wait.");
     try {
-      const unsubscribe = await listen("NewAccount", (event) => {
-        const [wallet_id, account_id, account_info] = event.payload as [
-          wallet_id: number,
-          account_id: number,
-          account_info: AccountType
-        ];
-        if (account_info && wallet_id === currentWalletId) {
-          addAccount(account_id, account_info);
-          notify("Account created successfully!", "success");
-        }
-        unsubscribe();
-      });
-      await invoke("new_account_wrapper", {
+      const [wallet_id, account_id, account_info] = await invokeAndWait<
+        [wallet_id: number, account_id: number, account_info: AccountType]
+      >("NewAccount", "new_account_wrapper", {
         request: {
           name: accountName,
           wallet_id: currentWalletId,
         },
       });
+      if (wallet_id === currentWalletId) {
+        addAccount(account_id, account_info);
+        notify("Account created successfully!", "success");
+      }
     } catch (error) {
-      notify(new String(error).toString(), "error");
+      notify(errorMessage(error), "error");
     }
     setLoading(false);
     setShowNewAccountModal(false);
@@ -969,6 +1042,13 @@ function Home() {
                   contentEditable={false}
                   className="w-full shadow-[1px] resize-none focus:outline-none focus:ring-2 focus:ring-blue-500 border border-gray-300 rounded-lg"
                 />
+                <p className="mb-4">Passphrase (optional)</p>
+                <input
+                  type="password"
+                  value={passphrase}
+                  onChange={(e) => setPassphrase(e.target.value)}
+                  className="w-full px-3 py-2 focus:outline-none focus:ring-2 focus:ring-blue-500 border border-gray-300 rounded-lg"
+                />
                 <button
                   className="bg-green-400 text-black w-full px-4 py-2 rounded-lg hover:bg-[#000000] hover:text-green-400 transition duration-200"
                   onClick={(e) => handleCreateNewWallet(e)}
@@ -1008,6 +1088,13 @@ function Home() {
                   onChange={(e) => setMnemonic(e.target.value)}
                   className="w-full shadow-[1px] resize-none focus:outline-none focus:ring-2 focus:ring-blue-500 border border-gray-300 rounded-lg"
                 />
+                <p className="mb-4">Passphrase (optional)</p>
+                <input
+                  type="password"
+                  value={passphrase}
+                  onChange={(e) => setPassphrase(e.target.value)}
+                  className="w-full px-3 py-2 focus:outline-none focus:ring-2 focus:ring-blue-500 border border-gray-300 rounded-lg"
+                />
                 <button
                   className="bg-green-400 text-black w-full px-4 py-2 rounded-lg hover:bg-[#000000] hover:text-green-400 transition duration-200"
                   onClick={handleRecoverWallet}
@@ -1052,6 +1139,28 @@ function Home() {
                 >
                   Exit
                 </button>
+                <label className="flex items-center space-x-2 px-1 text-[#000000]">
+                  <input
+                    type="checkbox"
+                    checked={settings?.auto_start ?? false}
+                    onChange={(e) =>
+                      updateSettings({ auto_start: e.target.checked })
+                    }
+                  />
+                  <span>
+                    Start on {netMode} in {walletMode} mode next time
+                  </span>
+                </label>
+                <label className="flex items-center space-x-2 px-1 text-[#000000]">
+                  <input
+                    type="checkbox"
+                    checked={settings?.reopen_last_wallet ?? false}
+                    onChange={(e) =>
+                      updateSettings({ reopen_last_wallet: e.target.checked })
+                    }
+                  />
+                  <span>Reopen the last wallet on start</span>
+                </label>
                 <hr className="my-12 h-[2px] bg-gradient-to-r from-transparent via-neutral-500 to-transparent opacity-25 dark:via-neutral-800" />
               </>
               {walletsInfo.length !== 0 && (
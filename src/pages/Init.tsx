@@ -19,7 +19,7 @@ import { invoke } from "@tauri-apps/api/core";
 import { Menu } from "@tauri-apps/api/menu";
 import MintlayerIcon from "../assets/mintlayer_icon.png";
 import { LogicalPosition } from "@tauri-apps/api/dpi";
-import { ChainInfoType } from "../types/Types";
+import { ChainInfoType, Settings } from "../types/Types";
 import { notify } from "../utils/util";
 
 const Init = () => {
@@ -38,6 +38,20 @@ const Init = () => {
     };
     window.addEventListener("beforeunload", handleBeforeUnload);
   }, []);
+  useEffect(() => {
+    const auto_start = async () => {
+      try {
+        const settings: Settings = await invoke("get_settings");
+        if (settings.auto_start && settings.network && settings.wallet_mode) {
+          setNetMod(settings.network);
+          setWalletMode(settings.wallet_mode);
+        }
+      } catch (err) {
+        console.error("Error loading settings: ", err);
+      }
+    };
+    auto_start();
+  }, []);
   useEffect(() => {
     const init_node = async () => {
       try {
@@ -264,6 +264,8 @@ export type TransactionData = {
     tx: { tx: string };
   };
   serialized_tx: Transaction;
+  // Set when the console broadcast setting could not be restored after building it
+  broadcast_error?: { code: ErrorCode; message: string };
 };
 
 export type DelegateStakingResult = {
@@ -275,6 +277,239 @@ export type DelegateStakingResult = {
   delegation_id: string;
 };
 
+export type ResponseEvent<T> = {
+  request_id: number | null;
+  data: T;
+};
+
+export type ErrorCode =
+  | "NodeNotInitialized"
+  | "NodeAlreadyInitialized"
+  | "NodeBusy"
+  | "InvalidInput"
+  | "InvalidAddress"
+  | "InvalidAmount"
+  | "InsufficientFunds"
+  | "WalletLocked"
+  | "UnknownWallet"
+  | "UnknownAccount"
+  | "Wallet"
+  | "Unsupported"
+  | "Storage"
+  | "Backend";
+
+export type NodeState =
+  | "Uninitialized"
+  | "Starting"
+  | "Running"
+  | "Stopping"
+  | "Stopped";
+
+export type ErrorEvent = {
+  request_id: number | null;
+  code: ErrorCode;
+  event: string | null;
+  wallet_id: number | null;
+  account_id: number | null;
+  message: string;
+};
+
+export type MnemonicValidation = {
+  valid: boolean;
+  word_count: number;
+  word_count_valid: boolean;
+  invalid_words: { index: number; word: string; suggestions: string[] }[];
+  checksum_valid: boolean | null;
+  error: string | null;
+};
+
+export type FeeRate = "Low" | "Normal" | "High" | { PerKb: string };
+
+export type FeeEstimate = {
+  inputs: { id: { Transaction: string }; index: number }[];
+  payment: { atoms: string };
+  change: { atoms: string };
+  fee: { atoms: string };
+  total_debit: { atoms: string };
+};
+
+export type PreparedTransaction = {
+  transaction: TransactionData;
+  estimate: FeeEstimate;
+};
+
+export type PaymentOutput = {
+  address: string;
+  amount: string;
+  // Rejected by the backend, batches pay coins only
+  token?: string | null;
+};
+
+export type PayoutPreview = {
+  valid: boolean;
+  rows: { line: number; label: string | null; output: PaymentOutput }[];
+  errors: { line: number; message: string }[];
+  total: { atoms: string } | null;
+};
+
+export type UtxoEntry = {
+  outpoint: { id: { Transaction: string }; index: number };
+  amount: { atoms: string } | null;
+  address: string | null;
+  locked: boolean;
+  confirmations: number | null;
+  output: unknown;
+};
+
+export type TokenInfo = {
+  token_id: string;
+  ticker: string;
+  decimals: number;
+  metadata_uri: string;
+  authority: string | null;
+  circulating_supply: { atoms: string } | null;
+  total_supply: unknown;
+  is_locked: boolean;
+  frozen: boolean;
+};
+
+export type TokenSupply = "Unlimited" | "Lockable" | { Fixed: string };
+
+export type TokenIssuance = {
+  ticker: string;
+  decimals: number;
+  metadata_uri: string;
+  destination_address: string;
+  supply: TokenSupply;
+  freezable: boolean;
+};
+
+export type TokenAction =
+  | { Mint: { address: string; amount: string } }
+  | { Unmint: { amount: string } }
+  | "LockSupply"
+  | { Freeze: { unfreezable: boolean } }
+  | "Unfreeze"
+  | { ChangeAuthority: { address: string } }
+  | { ChangeMetadataUri: { metadata_uri: string } };
+
+export type NftIssuance = {
+  name: string;
+  ticker: string;
+  description: string;
+  media_hash: string;
+  destination_address: string;
+  media_uri?: string;
+  icon_uri?: string;
+  additional_metadata_uri?: string;
+};
+
+export type NftInfo = {
+  token_id: string;
+  name: string;
+  ticker: string;
+  description: string;
+  media_hash: string;
+  media_uri: string | null;
+  icon_uri: string | null;
+  additional_metadata_uri: string | null;
+  creator: string | null;
+};
+
+export type MessageVerification = {
+  valid: boolean;
+  error: string | null;
+};
+
+export type Contact = {
+  label: string;
+  address: string;
+};
+
+export type TransactionFileResult = {
+  transaction: string;
+  summary: string;
+  signed: TransactionData | null;
+};
+
+export type DecodedTransaction = {
+  tx_id: string;
+  signed: boolean;
+  inputs: {
+    input: Input;
+    signature: "Signed" | "Unsigned";
+    amount: { atoms: string } | null;
+    owner: { wallet_id: number; account_id: number } | null;
+  }[];
+  outputs: {
+    output: Output;
+    amount: { atoms: string } | null;
+    address: string | null;
+    token_id: string | null;
+  }[];
+  fee: { atoms: string } | null;
+  transaction: Transaction;
+};
+
+export type BroadcastResult = {
+  tx_id: string;
+  accepted: boolean;
+  rejection: { reason: RejectionReason; message: string } | null;
+};
+
+export type RejectionReason =
+  | "FeeTooLow"
+  | "Conflict"
+  | "MempoolFull"
+  | "Orphan"
+  | "Invalid";
+
+export type TxKind = "Received" | "Sent" | "Redeposit" | "Other";
+
+export type HistoryQuery = {
+  kinds?: TxKind[];
+  from_timestamp?: number;
+  to_timestamp?: number;
+  min_amount?: string;
+  max_amount?: string;
+  txid?: string;
+  address?: string;
+  sort?: "NewestFirst" | "OldestFirst" | "LargestFirst" | "SmallestFirst";
+  skip?: number;
+  count?: number;
+};
+
+export type HistoryPage = {
+  total: number;
+  matching: number;
+  skip: number;
+  txs: TransactionInfoType[];
+  received: { atoms: string };
+  sent: { atoms: string };
+};
+
+export type QrDecodeResult = {
+  frame_count: number | null;
+  missing: number[];
+  rejected: { frame: string; reason: string }[];
+  transaction: string | null;
+  error: string | null;
+};
+
+export type Settings = {
+  version: number;
+  network: "Mainnet" | "Testnet" | null;
+  wallet_mode: "Hot" | "Cold" | null;
+  auto_start: boolean;
+  reopen_last_wallet: boolean;
+};
+
+export type RecentWallet = {
+  file_path: string;
+  wallet_type: "Hot" | "Cold";
+  last_opened: number;
+};
+
 type SetStatus = {
   SetStatus: { status: string; print_message: string };
 };
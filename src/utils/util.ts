@@ -14,8 +14,11 @@
 // limitations under the License.
 
 import { toast } from "react-toastify";
+import { invoke, InvokeArgs } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import * as blake from "blakejs";
 import { bech32m } from "bech32";
+import { ErrorEvent, ResponseEvent } from "../types/Types";
 
 export const DECIMAL = 100000000000;
 
@@ -68,6 +71,66 @@ const convertTo5BitWords = (data: ArrayLike<number>): number[] => {
 
   return words;
 };
+export const errorMessage = (error: unknown): string => {
+  if (typeof error === "object" && error !== null && "message" in error) {
+    return String(error.message);
+  }
+  return String(error);
+};
+
+// Invokes a command which answers with a request id, and waits for the `eventName`
+// event or the error event carrying that id.
+export const invokeAndWait = async <T>(
+  eventName: string,
+  command: string,
+  args?: InvokeArgs
+): Promise<T> => {
+  let requestId: number | undefined;
+  let resolveResult: (data: T) => void = () => {};
+  let rejectResult: (error: ErrorEvent) => void = () => {};
+  const result = new Promise<T>((resolve, reject) => {
+    resolveResult = resolve;
+    rejectResult = reject;
+  });
+
+  // The events may arrive before `invoke` returns the request id
+  const responses: ResponseEvent<T>[] = [];
+  const errors: ErrorEvent[] = [];
+  const settle = () => {
+    const error = errors.find((error) => error.request_id === requestId);
+    if (error) {
+      rejectResult(error);
+      return;
+    }
+    const response = responses.find(
+      (response) => response.request_id === requestId
+    );
+    if (response) {
+      resolveResult(response.data);
+    }
+  };
+
+  const unlistenResponse = await listen<ResponseEvent<T>>(
+    eventName,
+    (event) => {
+      responses.push(event.payload);
+      if (requestId !== undefined) settle();
+    }
+  );
+  const unlistenError = await listen<ErrorEvent>("Error", (event) => {
+    errors.push(event.payload);
+    if (requestId !== undefined) settle();
+  });
+  try {
+    requestId = await invoke<number>(command, args);
+    settle();
+    return await result;
+  } finally {
+    unlistenResponse();
+    unlistenError();
+  }
+};
+
 export const notify = (message: string, type: string) => {
   console.log("notification is displayed");
   switch (type) {